@@ -1,29 +1,171 @@
 use polars::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::fs::{File, FileTimes};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use crate::config::{CacheMode, get_config};
+use crate::catalog::{find_dataset_by_url, lookup_dataset};
+use crate::config::{CacheMode, Config, get_config};
+use crate::downloader::{DataFormat, Repository, parse_dataframe};
+use crate::error::{NflReadError, Result};
+
+/// Metadata persisted next to each filesystem cache entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheMetadata {
+    /// The URL the entry was downloaded from.
+    pub url: String,
+    /// The response's `ETag` header, used for conditional revalidation.
+    pub etag: Option<String>,
+    /// The response's `Last-Modified` header, used for conditional revalidation.
+    pub last_modified: Option<String>,
+    /// Format of a raw entry's data file, as downloaded. `None` for entries
+    /// stored as re-encoded Parquet.
+    pub format: Option<DataFormat>,
+    /// MD5 checksum of a raw entry's data file, taken when it is written and
+    /// verified whenever the entry is decoded.
+    pub md5: Option<String>,
+}
+
+/// A filesystem cache entry, as listed by [`cache_entries`].
+#[derive(Debug, Clone)]
+pub struct CacheEntryInfo {
+    pub key: String,
+    pub path: PathBuf,
+    /// The URL the entry was downloaded from, if its metadata is readable.
+    pub url: Option<String>,
+    /// The catalog dataset the URL belongs to.
+    pub dataset: Option<&'static str>,
+    /// Size in bytes of the cached data file.
+    pub size: u64,
+    /// MD5 checksum of the data file, recorded for raw entries. As the file
+    /// holds the download verbatim, this matches the upstream file's MD5.
+    pub md5: Option<String>,
+    pub age_secs: u64,
+    /// Seconds since the entry was last read or written.
+    pub idle_secs: u64,
+    /// Seconds until the entry expires; zero once it has.
+    pub expires_in_secs: u64,
+}
+
+impl CacheEntryInfo {
+    pub fn is_expired(&self) -> bool {
+        self.expires_in_secs == 0
+    }
+}
 
 struct CacheEntry {
     df: DataFrame,
+    url: String,
     timestamp: u64,
+    /// Estimated in-memory size of `df` in bytes.
+    size: usize,
+    /// Value of the cache's access counter when the entry was last read or
+    /// written, for LRU eviction.
+    last_used: u64,
 }
 
 struct MemoryCache {
     entries: HashMap<String, CacheEntry>,
+    total_bytes: usize,
+    clock: u64,
 }
 
 impl MemoryCache {
     fn new() -> Self {
         MemoryCache {
             entries: HashMap::new(),
+            total_bytes: 0,
+            clock: 0,
         }
     }
+
+    /// Look up an entry regardless of its age, marking it as recently used.
+    fn get(&mut self, key: &str) -> Option<&CacheEntry> {
+        self.clock += 1;
+        let entry = self.entries.get_mut(key)?;
+        entry.last_used = self.clock;
+        Some(entry)
+    }
+
+    /// Insert an entry, then evict least recently used entries until the
+    /// cache fits in `max_bytes` (`0` for no limit). An entry larger than
+    /// the whole budget is not cached.
+    fn insert(&mut self, key: String, df: DataFrame, url: String, max_bytes: usize) {
+        self.remove(&key);
+        let size = df.estimated_size();
+        if max_bytes > 0 && size > max_bytes {
+            log::warn!(
+                "not caching {key} in memory: its {size} bytes exceed memory_cache_max_bytes \
+                 ({max_bytes})"
+            );
+            return;
+        }
+        self.clock += 1;
+        self.total_bytes += size;
+        self.entries.insert(
+            key,
+            CacheEntry {
+                df,
+                url,
+                timestamp: now_secs(),
+                size,
+                last_used: self.clock,
+            },
+        );
+        while max_bytes > 0 && self.total_bytes > max_bytes {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            self.remove(&oldest);
+        }
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.total_bytes -= entry.size;
+        }
+    }
+
+    /// Keep only the entries matching `keep`, returning how many were removed.
+    fn retain(&mut self, mut keep: impl FnMut(&str, &CacheEntry) -> bool) -> usize {
+        let before = self.entries.len();
+        let mut freed = 0;
+        self.entries.retain(|key, entry| {
+            let kept = keep(key, entry);
+            if !kept {
+                freed += entry.size;
+            }
+            kept
+        });
+        self.total_bytes -= freed;
+        before - self.entries.len()
+    }
+
+    fn purge_expired(&mut self, cache_duration: u64) -> usize {
+        let now = now_secs();
+        self.retain(|_, entry| now.saturating_sub(entry.timestamp) < cache_duration)
+    }
 }
 
+/// Advisory lock file in the root of the cache directory.
+const LOCK_FILE: &str = ".lock";
+/// Suffix of raw entries' data files in formats other than Parquet.
+const RAW_SUFFIX: &str = ".raw";
+/// Suffix of the metadata sidecar next to each entry's data file.
+const METADATA_SUFFIX: &str = ".meta.toml";
+/// Suffix of in-progress writes, renamed into place once complete.
+const TEMP_SUFFIX: &str = ".tmp";
+
 static MEMORY_CACHE: OnceLock<Mutex<MemoryCache>> = OnceLock::new();
 
 fn get_memory_cache() -> &'static Mutex<MemoryCache> {
@@ -37,8 +179,59 @@ fn now_secs() -> u64 {
         .as_secs()
 }
 
+/// The cache key for `url`: `{repository}/{release path}` for release
+/// assets, e.g. `nflverse-data/pbp/play_by_play_2023`, or `{host}/{path}`
+/// for any other URL. A `.parquet` extension is dropped since it is implied
+/// by the cache file name; other extensions are kept. Keys double as relative
+/// paths under the cache directory.
 pub fn make_cache_key(url: &str) -> String {
-    format!("{:x}", md5::compute(url))
+    make_cache_key_in(url, &get_config())
+}
+
+/// [`make_cache_key`] with the repository base URLs of `config`.
+pub fn make_cache_key_in(url: &str, config: &Config) -> String {
+    if let Some((repo, path)) = Repository::from_url(url, config) {
+        return join_key_segments(repo.name(), path);
+    }
+    let Ok(parsed) = reqwest::Url::parse(url) else {
+        return format!("{:x}", md5::compute(url));
+    };
+    let host = match (parsed.host_str(), parsed.port()) {
+        (Some(host), Some(port)) => format!("{host}_{port}"),
+        (Some(host), None) => host.to_string(),
+        (None, _) => parsed.scheme().to_string(),
+    };
+    let mut key = join_key_segments(&host, parsed.path());
+    if let Some(query) = parsed.query() {
+        let digest = format!("{:x}", md5::compute(query));
+        key.push_str(&format!("_{}", &digest[..8]));
+    }
+    key
+}
+
+fn join_key_segments(root: &str, path: &str) -> String {
+    let path = path.strip_suffix(".parquet").unwrap_or(path);
+    let mut key = sanitize_key_segment(root);
+    for segment in path
+        .split('/')
+        .filter(|s| !s.is_empty() && *s != "." && *s != "..")
+    {
+        key.push('/');
+        key.push_str(&sanitize_key_segment(segment));
+    }
+    key
+}
+
+/// Replace anything but ASCII letters, digits, `.`, `_` and `-` so a key
+/// segment is a safe file name on every platform.
+fn sanitize_key_segment(segment: &str) -> String {
+    segment
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '_' | '-' => c,
+            _ => '_',
+        })
+        .collect()
 }
 
 pub fn cache_get(key: &str) -> Option<DataFrame> {
@@ -46,33 +239,27 @@ pub fn cache_get(key: &str) -> Option<DataFrame> {
     match config.cache_mode {
         CacheMode::Off => None,
         CacheMode::Memory => {
-            let cache = get_memory_cache().lock().unwrap();
-            if let Some(entry) = cache.entries.get(key) {
-                if now_secs() - entry.timestamp < config.cache_duration {
-                    return Some(entry.df.clone());
-                }
+            let mut cache = get_memory_cache().lock().unwrap();
+            // Expired entries are only worth keeping as a fallback.
+            if !config.offline && !config.stale_if_error {
+                cache.purge_expired(config.cache_duration);
             }
-            None
+            cache
+                .get(key)
+                .filter(|entry| now_secs().saturating_sub(entry.timestamp) < config.cache_duration)
+                .map(|entry| entry.df.clone())
         }
         CacheMode::Filesystem => {
-            let path = cache_file_path(&config.cache_dir, key);
-            if path.exists() {
-                if let Ok(metadata) = std::fs::metadata(&path) {
-                    if let Ok(modified) = metadata.modified() {
-                        let age = SystemTime::now()
-                            .duration_since(modified)
-                            .unwrap_or_default()
-                            .as_secs();
-                        if age < config.cache_duration {
-                            if let Ok(df) = ParquetReader::new(
-                                std::fs::File::open(&path).unwrap(),
-                            )
-                            .finish()
-                            {
-                                return Some(df);
-                            }
-                        }
-                    }
+            let path = data_file_path(&config.cache_dir, key);
+            if let Ok(metadata) = std::fs::metadata(&path)
+                && let Ok(modified) = metadata.modified()
+            {
+                let age = SystemTime::now()
+                    .duration_since(modified)
+                    .unwrap_or_default()
+                    .as_secs();
+                if age < config.cache_duration {
+                    return read_entry(&config.cache_dir, key);
                 }
             }
             None
@@ -80,67 +267,739 @@ pub fn cache_get(key: &str) -> Option<DataFrame> {
     }
 }
 
-pub fn cache_set(key: &str, df: &DataFrame) {
+pub fn cache_set(key: &str, df: &DataFrame, metadata: &CacheMetadata) {
     let config = get_config();
     match config.cache_mode {
         CacheMode::Off => {}
         CacheMode::Memory => {
             let mut cache = get_memory_cache().lock().unwrap();
-            cache.entries.insert(
+            if !config.offline && !config.stale_if_error {
+                cache.purge_expired(config.cache_duration);
+            }
+            cache.insert(
                 key.to_string(),
-                CacheEntry {
-                    df: df.clone(),
-                    timestamp: now_secs(),
-                },
+                df.clone(),
+                metadata.url.clone(),
+                config.memory_cache_max_bytes,
             );
         }
         CacheMode::Filesystem => {
-            let path = cache_file_path(&config.cache_dir, key);
-            if let Some(parent) = path.parent() {
-                let _ = std::fs::create_dir_all(parent);
+            if let Err(e) = write_entry(&config.cache_dir, key, EntryData::Frame(df), metadata)
+                && config.verbose
+            {
+                eprintln!("Failed to cache {}: {e}", metadata.url);
             }
-            if let Ok(file) = std::fs::File::create(&path) {
-                let _ = ParquetWriter::new(file).finish(&mut df.clone());
+            if config.disk_cache_max_bytes > 0 {
+                let _lock = lock_cache_dir(&config.cache_dir, true);
+                evict_over_quota(&config.cache_dir, config.disk_cache_max_bytes, key);
             }
         }
     }
 }
 
-pub fn clear_cache(pattern: Option<&str>) {
+/// Cache a download as its raw response body, which is parsed again on
+/// every read. Outside filesystem mode this is the same as [`cache_set`].
+pub fn cache_set_raw(
+    key: &str,
+    bytes: &[u8],
+    format: DataFormat,
+    df: &DataFrame,
+    metadata: &CacheMetadata,
+) {
     let config = get_config();
+    if config.cache_mode != CacheMode::Filesystem {
+        return cache_set(key, df, metadata);
+    }
+    if let Err(e) = write_entry(
+        &config.cache_dir,
+        key,
+        EntryData::Raw(bytes, format),
+        metadata,
+    ) && config.verbose
+    {
+        eprintln!("Failed to cache {}: {e}", metadata.url);
+    }
+    if config.disk_cache_max_bytes > 0 {
+        let _lock = lock_cache_dir(&config.cache_dir, true);
+        evict_over_quota(&config.cache_dir, config.disk_cache_max_bytes, key);
+    }
+}
 
-    // Clear memory cache
+/// Cache a downloaded Parquet file verbatim, without decoding it, and open
+/// the new entry with [`cache_file`] for scanning. Returns None outside
+/// filesystem mode or when the entry cannot be written.
+pub fn cache_set_file(key: &str, bytes: &[u8], metadata: &CacheMetadata) -> Option<File> {
+    let config = get_config();
+    if config.cache_mode != CacheMode::Filesystem {
+        return None;
+    }
+    let data = EntryData::Raw(bytes, DataFormat::Parquet);
+    if let Err(e) = write_entry(&config.cache_dir, key, data, metadata) {
+        if config.verbose {
+            eprintln!("Failed to cache {}: {e}", metadata.url);
+        }
+        return None;
+    }
+    if config.disk_cache_max_bytes > 0 {
+        let _lock = lock_cache_dir(&config.cache_dir, true);
+        evict_over_quota(&config.cache_dir, config.disk_cache_max_bytes, key);
+    }
+    cache_file(key)
+}
+
+/// The Parquet data file of a fresh filesystem cache entry, opened for
+/// scanning lazily instead of reading it in full. The open file pins the
+/// entry's contents, so a scan is unaffected by the entry being evicted or
+/// replaced before it runs. Only the file's footer is checked up front; a
+/// raw entry's checksum is verified if that fails.
+/// Returns None outside filesystem mode, or when the entry is missing,
+/// expired, corrupt or not stored as Parquet.
+pub fn cache_file(key: &str) -> Option<File> {
+    let config = get_config();
+    if config.cache_mode != CacheMode::Filesystem {
+        return None;
+    }
+    let path = cache_file_path(&config.cache_dir, key);
+    let (file, metadata, version) = {
+        let _lock = lock_cache_dir(&config.cache_dir, false);
+        let file = File::open(&path).ok()?;
+        (
+            file,
+            read_metadata(&config.cache_dir, key),
+            file_version(&path),
+        )
+    };
+    let modified = file.metadata().ok()?.modified().ok()?;
+    let age = SystemTime::now()
+        .duration_since(modified)
+        .unwrap_or_default()
+        .as_secs();
+    if age >= config.cache_duration {
+        return None;
+    }
+    // The checksum is taken from the bytes as they are written, so hashing
+    // the whole file again here would cost as much as the read it precedes.
+    // Checking that the footer still decodes catches a truncated entry.
+    if let Err(e) = read_footer(&file, metadata.as_ref()) {
+        discard_entry(&config.cache_dir, key, version, e);
+        return None;
+    }
+    mark_used(&path);
+    Some(file)
+}
+
+/// Decode the Parquet footer of a cache entry's data file. If it does not
+/// decode, the file's checksum, when recorded, is compared to tell a
+/// corrupted entry from an upstream file that was never valid.
+fn read_footer(file: &File, metadata: Option<&CacheMetadata>) -> Result<()> {
+    let Err(e) = ParquetReader::new(file.try_clone()?)
+        .get_metadata()
+        .map(|_| ())
+    else {
+        return Ok(());
+    };
+    if let Some(expected) = metadata.and_then(|m| m.md5.as_deref()) {
+        let mut context = md5::Context::new();
+        std::io::copy(&mut &*file, &mut context)?;
+        let actual = format!("{:x}", context.compute());
+        if actual != expected {
+            return Err(checksum_error(&actual, expected));
+        }
+    }
+    Err(e.into())
+}
+
+/// Return a cached DataFrame regardless of its age, along with that age in
+/// seconds.
+///
+/// The memory cache is checked first, then the filesystem cache directory.
+/// Unlike [`cache_get`], the filesystem is also consulted in memory mode, so
+/// entries written by an earlier filesystem-mode session can be reused. With
+/// [`CacheMode::Off`] no cache is read and this always returns None.
+pub fn cache_get_stale(key: &str) -> Option<(DataFrame, u64)> {
+    if get_config().cache_mode == CacheMode::Off {
+        return None;
+    }
     {
         let mut cache = get_memory_cache().lock().unwrap();
-        match pattern {
-            None => cache.entries.clear(),
-            Some(pat) => cache.entries.retain(|k, _| !k.contains(pat)),
+        if let Some(entry) = cache.get(key) {
+            return Some((entry.df.clone(), now_secs().saturating_sub(entry.timestamp)));
+        }
+    }
+
+    let config = get_config();
+    let path = data_file_path(&config.cache_dir, key);
+    let modified = std::fs::metadata(&path).ok()?.modified().ok()?;
+    let age = SystemTime::now()
+        .duration_since(modified)
+        .unwrap_or_default()
+        .as_secs();
+    read_entry(&config.cache_dir, key).map(|df| (df, age))
+}
+
+/// Return the stored metadata for an expired filesystem cache entry, so the
+/// download can be revalidated with a conditional request.
+///
+/// Returns None outside filesystem mode, when there is no entry, or when the
+/// entry has neither an ETag nor a Last-Modified validator.
+pub fn cache_validators(key: &str) -> Option<CacheMetadata> {
+    let config = get_config();
+    if config.cache_mode != CacheMode::Filesystem
+        || !data_file_path(&config.cache_dir, key).exists()
+    {
+        return None;
+    }
+    let metadata = read_metadata(&config.cache_dir, key)?;
+    if metadata.etag.is_none() && metadata.last_modified.is_none() {
+        return None;
+    }
+    Some(metadata)
+}
+
+/// Mark a filesystem cache entry as fresh again after the server confirmed it
+/// is unchanged (HTTP 304), and return its contents.
+///
+/// `validators` are those of the 304 response; any it carries replace the
+/// entry's stored ones, so the next revalidation sends the current values.
+pub fn cache_revalidate(key: &str, validators: &CacheMetadata) -> Option<DataFrame> {
+    if !cache_refresh(key, validators) {
+        return None;
+    }
+    read_entry(&get_config().cache_dir, key)
+}
+
+/// [`cache_revalidate`] without reading the entry back, for callers that scan
+/// it with [`cache_file`] instead. Returns whether the entry was refreshed.
+pub fn cache_refresh(key: &str, validators: &CacheMetadata) -> bool {
+    let config = get_config();
+    let path = data_file_path(&config.cache_dir, key);
+    if let Some(stored) = read_metadata(&config.cache_dir, key) {
+        let updated = CacheMetadata {
+            etag: validators.etag.clone().or(stored.etag.clone()),
+            last_modified: validators
+                .last_modified
+                .clone()
+                .or(stored.last_modified.clone()),
+            ..stored.clone()
+        };
+        if updated != stored {
+            let _ = write_metadata(&config.cache_dir, key, &updated);
         }
     }
+    let Ok(file) = std::fs::File::options().write(true).open(&path) else {
+        return false;
+    };
+    let now = SystemTime::now();
+    file.set_times(FileTimes::new().set_modified(now).set_accessed(now))
+        .is_ok()
+}
+
+/// Clear cached data. With a pattern, only entries whose key contains it are
+/// removed, e.g. `"pbp/"` or `"play_by_play_2023"`.
+pub fn clear_cache(pattern: Option<&str>) {
+    let config = get_config();
+
+    // Clear memory cache
+    get_memory_cache()
+        .lock()
+        .unwrap()
+        .retain(|k, _| pattern.is_some_and(|pat| !k.contains(pat)));
 
     // Clear filesystem cache
-    if config.cache_mode == CacheMode::Filesystem {
-        if config.cache_dir.exists() {
-            match pattern {
-                None => {
-                    let _ = std::fs::remove_dir_all(&config.cache_dir);
-                    let _ = std::fs::create_dir_all(&config.cache_dir);
+    if config.cache_mode == CacheMode::Filesystem && config.cache_dir.exists() {
+        let _lock = lock_cache_dir(&config.cache_dir, true);
+        for path in cache_files(&config.cache_dir) {
+            let Ok(relative) = path.strip_prefix(&config.cache_dir) else {
+                continue;
+            };
+            let relative = relative_key(relative);
+            if relative == LOCK_FILE {
+                continue;
+            }
+            // Match entries by key too, so a pattern naming the data file also
+            // removes the sidecar, and sidecars left without data go as well.
+            let key = relative
+                .strip_suffix(".parquet")
+                .or_else(|| relative.strip_suffix(RAW_SUFFIX))
+                .or_else(|| relative.strip_suffix(METADATA_SUFFIX));
+            let matches = |name: &str| pattern.is_none_or(|pat| name.contains(pat));
+            match key {
+                Some(key) if matches(key) || matches(&relative) => {
+                    remove_file_entry(&config.cache_dir, key);
                 }
-                Some(_pat) => {
-                    if let Ok(entries) = std::fs::read_dir(&config.cache_dir) {
-                        for entry in entries.flatten() {
-                            let name = entry.file_name().to_string_lossy().to_string();
-                            if name.contains(_pat) {
-                                let _ = std::fs::remove_file(entry.path());
-                            }
-                        }
-                    }
+                None if matches(&relative) => {
+                    let _ = std::fs::remove_file(&path);
                 }
+                _ => {}
             }
         }
+        remove_empty_dirs(&config.cache_dir);
     }
 }
 
-fn cache_file_path(cache_dir: &PathBuf, key: &str) -> PathBuf {
+/// List the entries in the filesystem cache directory, oldest first.
+pub fn cache_entries() -> Vec<CacheEntryInfo> {
+    let config = get_config();
+    let mut entries: Vec<CacheEntryInfo> = cache_files(&config.cache_dir)
+        .into_iter()
+        .filter_map(|path| {
+            let relative = relative_key(path.strip_prefix(&config.cache_dir).ok()?);
+            let key = relative
+                .strip_suffix(".parquet")
+                .or_else(|| relative.strip_suffix(RAW_SUFFIX))?
+                .to_string();
+            let metadata = std::fs::metadata(&path).ok()?;
+            let age_secs = SystemTime::now()
+                .duration_since(metadata.modified().ok()?)
+                .unwrap_or_default()
+                .as_secs();
+            let idle_secs = metadata
+                .accessed()
+                .ok()
+                .and_then(|accessed| SystemTime::now().duration_since(accessed).ok())
+                .map_or(age_secs, |idle| idle.as_secs().min(age_secs));
+            let (url, md5) = read_metadata(&config.cache_dir, &key)
+                .map_or((None, None), |m| (Some(m.url), m.md5));
+            Some(CacheEntryInfo {
+                dataset: url
+                    .as_deref()
+                    .and_then(|url| find_dataset_by_url(url, &config))
+                    .map(|d| d.name),
+                url,
+                key,
+                path,
+                size: metadata.len(),
+                md5,
+                age_secs,
+                idle_secs,
+                expires_in_secs: config.cache_duration.saturating_sub(age_secs),
+            })
+        })
+        .collect();
+    entries.sort_by_key(|e| std::cmp::Reverse(e.age_secs));
+    entries
+}
+
+/// Remove every cache entry older than the cache duration, returning how
+/// many were removed. Directories left empty under the filesystem cache
+/// directory are removed too.
+pub fn prune_cache() -> usize {
+    let config = get_config();
+    let mut removed = get_memory_cache()
+        .lock()
+        .unwrap()
+        .purge_expired(config.cache_duration);
+
+    if config.cache_mode == CacheMode::Filesystem {
+        let _lock = lock_cache_dir(&config.cache_dir, true);
+        for entry in cache_entries().iter().filter(|e| e.is_expired()) {
+            remove_file_entry(&config.cache_dir, &entry.key);
+            removed += 1;
+        }
+        remove_abandoned_temp_files(&config.cache_dir);
+        remove_orphaned_sidecars(&config.cache_dir);
+        remove_empty_dirs(&config.cache_dir);
+    }
+    removed
+}
+
+/// Remove every cache entry downloaded for the catalog dataset `name`,
+/// returning how many were removed.
+pub fn clear_dataset_cache(name: &str) -> Result<usize> {
+    let dataset = lookup_dataset(name)?;
+
+    let config = get_config();
+    let mut removed = 0;
+    {
+        let mut cache = get_memory_cache().lock().unwrap();
+        removed += cache.retain(|_, entry| !dataset.matches_url(&entry.url, &config));
+    }
+
+    if config.cache_mode == CacheMode::Filesystem {
+        let _lock = lock_cache_dir(&config.cache_dir, true);
+        for entry in cache_entries()
+            .iter()
+            .filter(|e| e.dataset == Some(dataset.name))
+        {
+            remove_file_entry(&config.cache_dir, &entry.key);
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// Read a filesystem cache entry. An entry that exists but cannot be parsed,
+/// or whose checksum does not match, is deleted so it will be downloaded
+/// again.
+fn read_entry(cache_dir: &Path, key: &str) -> Option<DataFrame> {
+    let path = data_file_path(cache_dir, key);
+    // Hold the lock only while reading the files; decoding needs no lock.
+    let (bytes, metadata, version) = {
+        let _lock = lock_cache_dir(cache_dir, false);
+        let version = file_version(&path);
+        (
+            std::fs::read(&path).ok()?,
+            read_metadata(cache_dir, key),
+            version,
+        )
+    };
+    match decode_entry(&bytes, metadata.as_ref()) {
+        Ok(df) => {
+            mark_used(&path);
+            Some(df)
+        }
+        Err(e) => {
+            discard_entry(cache_dir, key, version, e);
+            None
+        }
+    }
+}
+
+/// Delete the corrupt entry `key`, unless its data file no longer has the
+/// `version` that was read because another writer replaced it meanwhile.
+fn discard_entry(
+    cache_dir: &Path,
+    key: &str,
+    version: Option<(SystemTime, u64)>,
+    error: NflReadError,
+) {
+    let path = data_file_path(cache_dir, key);
+    if get_config().verbose {
+        eprintln!(
+            "Discarding corrupted cache entry {}: {error}",
+            path.display()
+        );
+    }
+    let _lock = lock_cache_dir(cache_dir, true);
+    if file_version(&path) == version {
+        remove_file_entry(cache_dir, key);
+    }
+}
+
+/// The modification time and size of `path`, which change whenever a write
+/// renames a new data file into place.
+fn file_version(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+fn decode_entry(bytes: &[u8], metadata: Option<&CacheMetadata>) -> Result<DataFrame> {
+    if let Some(expected) = metadata.and_then(|m| m.md5.as_deref()) {
+        let actual = format!("{:x}", md5::compute(bytes));
+        if actual != expected {
+            return Err(checksum_error(&actual, expected));
+        }
+    }
+    let format = metadata
+        .and_then(|m| m.format)
+        .unwrap_or(DataFormat::Parquet);
+    parse_dataframe(bytes, format)
+}
+
+fn checksum_error(actual: &str, expected: &str) -> NflReadError {
+    NflReadError::Io(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("MD5 checksum {actual} does not match {expected}"),
+    ))
+}
+
+/// What a filesystem cache entry's data file holds.
+enum EntryData<'a> {
+    /// A parsed DataFrame, re-encoded as Parquet.
+    Frame(&'a DataFrame),
+    /// A download exactly as received.
+    Raw(&'a [u8], DataFormat),
+}
+
+/// Write an entry and its metadata sidecar to temporary files, then rename
+/// them into place, so a crash or a full disk never leaves a truncated entry
+/// and readers see either the old entry or the complete new one.
+fn write_entry(
+    cache_dir: &Path,
+    key: &str,
+    data: EntryData,
+    metadata: &CacheMetadata,
+) -> std::io::Result<()> {
+    let parquet_path = cache_file_path(cache_dir, key);
+    let raw_path = raw_file_path(cache_dir, key);
+    let metadata_path = metadata_file_path(cache_dir, key);
+    if let Some(parent) = parquet_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut metadata = metadata.clone();
+    let (path, stale_path, data_temp) = match data {
+        EntryData::Frame(df) => {
+            metadata.format = None;
+            metadata.md5 = None;
+            let temp = write_temp_file(&parquet_path, |file| {
+                ParquetWriter::new(file)
+                    .finish(&mut df.clone())
+                    .map(|_| ())
+                    .map_err(std::io::Error::other)
+            })?;
+            (parquet_path, raw_path, temp)
+        }
+        EntryData::Raw(bytes, format) => {
+            metadata.format = Some(format);
+            metadata.md5 = Some(format!("{:x}", md5::compute(bytes)));
+            // Raw Parquet is still Parquet; other formats get a `.raw` file.
+            let (path, stale_path) = match format {
+                DataFormat::Parquet => (parquet_path, raw_path),
+                _ => (raw_path, parquet_path),
+            };
+            let temp = write_temp_file(&path, |file| file.write_all(bytes))?;
+            (path, stale_path, temp)
+        }
+    };
+    let contents = toml::to_string(&metadata).map_err(std::io::Error::other);
+    let metadata_temp = match contents.and_then(|contents| {
+        write_temp_file(&metadata_path, |file| file.write_all(contents.as_bytes()))
+    }) {
+        Ok(temp) => temp,
+        Err(e) => {
+            let _ = std::fs::remove_file(&data_temp);
+            return Err(e);
+        }
+    };
+
+    let _lock = lock_cache_dir(cache_dir, true);
+    let renamed = std::fs::rename(&data_temp, &path)
+        .and_then(|()| std::fs::rename(&metadata_temp, &metadata_path));
+    match &renamed {
+        // Drop the entry's data file from before a switch of `cache_raw`.
+        Ok(()) => {
+            let _ = std::fs::remove_file(stale_path);
+        }
+        Err(_) => {
+            let _ = std::fs::remove_file(&data_temp);
+            let _ = std::fs::remove_file(&metadata_temp);
+        }
+    }
+    renamed
+}
+
+/// Replace the metadata sidecar of an existing entry.
+fn write_metadata(cache_dir: &Path, key: &str, metadata: &CacheMetadata) -> std::io::Result<()> {
+    let path = metadata_file_path(cache_dir, key);
+    let contents = toml::to_string(metadata).map_err(std::io::Error::other)?;
+    let temp = write_temp_file(&path, |file| file.write_all(contents.as_bytes()))?;
+    let _lock = lock_cache_dir(cache_dir, true);
+    std::fs::rename(&temp, &path).inspect_err(|_| {
+        let _ = std::fs::remove_file(&temp);
+    })
+}
+
+/// Write `bytes` to `path` through a temporary file renamed into place, so
+/// `path` never holds a partial write.
+pub(crate) fn write_file_atomically(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let temp = write_temp_file(path, |file| file.write_all(bytes))?;
+    std::fs::rename(&temp, path).inspect_err(|_| {
+        let _ = std::fs::remove_file(&temp);
+    })
+}
+
+/// Write a uniquely named temporary file next to `path` and flush it to disk,
+/// returning its path.
+fn write_temp_file(
+    path: &Path,
+    write: impl FnOnce(&mut File) -> std::io::Result<()>,
+) -> std::io::Result<PathBuf> {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(
+        ".{}.{}{TEMP_SUFFIX}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let temp = path.with_file_name(name);
+    let result = File::create(&temp).and_then(|mut file| {
+        write(&mut file)?;
+        file.sync_all()
+    });
+    match result {
+        Ok(()) => Ok(temp),
+        Err(e) => {
+            let _ = std::fs::remove_file(&temp);
+            Err(e)
+        }
+    }
+}
+
+/// Take an advisory lock on the cache directory, released when the returned
+/// file is dropped. Readers share it; writes and removals take it
+/// exclusively, so processes sharing a cache directory never remove an entry
+/// another is reading. Returns None, and callers proceed unlocked, when the
+/// lock file cannot be opened or the filesystem does not support locking.
+fn lock_cache_dir(cache_dir: &Path, exclusive: bool) -> Option<File> {
+    let file = File::options()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(cache_dir.join(LOCK_FILE))
+        .ok()?;
+    let locked = if exclusive {
+        file.lock()
+    } else {
+        file.lock_shared()
+    };
+    locked.ok().map(|()| file)
+}
+
+/// Remove temporary files more than an hour old, left behind by a process
+/// that died mid-write.
+fn remove_abandoned_temp_files(cache_dir: &Path) {
+    for path in cache_files(cache_dir) {
+        let abandoned = path.to_string_lossy().ends_with(TEMP_SUFFIX)
+            && std::fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .is_ok_and(|modified| modified.elapsed().is_ok_and(|age| age.as_secs() > 3600));
+        if abandoned {
+            let _ = std::fs::remove_file(&path);
+        }
+    }
+}
+
+/// Remove metadata sidecars whose entry has no data file, left behind when
+/// a data file was deleted by hand or by a process that died mid-removal.
+fn remove_orphaned_sidecars(cache_dir: &Path) {
+    for path in cache_files(cache_dir) {
+        let Ok(relative) = path.strip_prefix(cache_dir) else {
+            continue;
+        };
+        let relative = relative_key(relative);
+        let Some(key) = relative.strip_suffix(METADATA_SUFFIX) else {
+            continue;
+        };
+        if !cache_file_path(cache_dir, key).exists() && !raw_file_path(cache_dir, key).exists() {
+            let _ = std::fs::remove_file(&path);
+        }
+    }
+}
+
+/// Record a read of a filesystem cache entry in its access time, which
+/// drives LRU eviction; the modification time keeps tracking freshness.
+fn mark_used(path: &Path) {
+    if let Ok(file) = std::fs::File::options().write(true).open(path) {
+        let _ = file.set_times(FileTimes::new().set_accessed(SystemTime::now()));
+    }
+}
+
+/// Remove least recently used entries until the cache directory fits in
+/// `max_bytes`, keeping the just-written entry `keep`. Every file in the
+/// directory counts toward the quota: data files, metadata sidecars and
+/// temporary files of writes in progress or abandoned. Orphaned sidecars and
+/// abandoned temporary files are removed first, as evicting entries cannot
+/// reclaim their space.
+fn evict_over_quota(cache_dir: &Path, max_bytes: u64, keep: &str) {
+    remove_abandoned_temp_files(cache_dir);
+    remove_orphaned_sidecars(cache_dir);
+    let mut total: u64 = cache_files(cache_dir)
+        .iter()
+        .map(|path| file_size(path))
+        .sum();
+    if total <= max_bytes {
+        return;
+    }
+    let mut entries = cache_entries();
+    entries.sort_by_key(|e| std::cmp::Reverse(e.idle_secs));
+    for entry in entries.iter().filter(|e| e.key != keep) {
+        if total <= max_bytes {
+            break;
+        }
+        let files = entry_files(cache_dir, &entry.key);
+        let size: u64 = files.iter().map(|path| file_size(path)).sum();
+        remove_file_entry(cache_dir, &entry.key);
+        total = total.saturating_sub(size);
+    }
+}
+
+fn file_size(path: &Path) -> u64 {
+    std::fs::metadata(path).map_or(0, |m| m.len())
+}
+
+/// Remove empty directories below `dir`, keeping `dir` itself.
+fn remove_empty_dirs(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            remove_empty_dirs(&entry.path());
+            // Fails, harmlessly, unless the directory is now empty.
+            let _ = std::fs::remove_dir(entry.path());
+        }
+    }
+}
+
+fn remove_file_entry(cache_dir: &Path, key: &str) {
+    for path in entry_files(cache_dir, key) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// The files an entry may consist of: its data file in either form and its
+/// metadata sidecar.
+fn entry_files(cache_dir: &Path, key: &str) -> [PathBuf; 3] {
+    [
+        cache_file_path(cache_dir, key),
+        raw_file_path(cache_dir, key),
+        metadata_file_path(cache_dir, key),
+    ]
+}
+
+/// Every file under `dir`, recursively.
+fn cache_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            match entry.file_type() {
+                Ok(t) if t.is_dir() => pending.push(entry.path()),
+                Ok(_) => files.push(entry.path()),
+                Err(_) => {}
+            }
+        }
+    }
+    files
+}
+
+/// A path relative to the cache directory, with `/` separators.
+fn relative_key(relative: &Path) -> String {
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn cache_file_path(cache_dir: &Path, key: &str) -> PathBuf {
     cache_dir.join(format!("{key}.parquet"))
 }
+
+/// Data file of a raw entry in a format other than Parquet.
+fn raw_file_path(cache_dir: &Path, key: &str) -> PathBuf {
+    cache_dir.join(format!("{key}{RAW_SUFFIX}"))
+}
+
+/// The data file of an existing entry, or where a Parquet entry would go.
+fn data_file_path(cache_dir: &Path, key: &str) -> PathBuf {
+    let raw = raw_file_path(cache_dir, key);
+    if raw.exists() {
+        raw
+    } else {
+        cache_file_path(cache_dir, key)
+    }
+}
+
+fn metadata_file_path(cache_dir: &Path, key: &str) -> PathBuf {
+    cache_dir.join(format!("{key}{METADATA_SUFFIX}"))
+}
+
+fn read_metadata(cache_dir: &Path, key: &str) -> Option<CacheMetadata> {
+    let contents = std::fs::read_to_string(metadata_file_path(cache_dir, key)).ok()?;
+    toml::from_str(&contents).ok()
+}
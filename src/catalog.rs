@@ -0,0 +1,659 @@
+//! The catalog of every dataset the crate can load: where each one lives,
+//! which seasons and parameters it accepts, and how its release paths are
+//! built. Loaders resolve their paths through these entries.
+
+use crate::config::Config;
+use crate::downloader::{DataFormat, Repository};
+use crate::error::{NflReadError, Result};
+use crate::loaders::seasons::Seasons;
+use crate::utils_date::get_current_season;
+
+/// Which date decides the current season for a dataset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeasonCutoff {
+    /// The Thursday after Labor Day.
+    Standard,
+    /// March 15, used for roster-related datasets.
+    Roster,
+}
+
+/// A parameter accepted by a dataset, with its valid values.
+#[derive(Debug, Clone, Copy)]
+pub struct Parameter {
+    pub name: &'static str,
+    pub values: &'static [&'static str],
+    /// The value used when none is given.
+    pub default: &'static str,
+}
+
+/// A release file (or family of per-season files) that makes up a dataset.
+#[derive(Debug, Clone, Copy)]
+pub struct DatasetFile {
+    /// Release path relative to the repository base URL. `{season}` and
+    /// `{<parameter>}` placeholders are substituted when resolving.
+    pub path_template: &'static str,
+    pub format: DataFormat,
+    /// Only used when the named parameter has the given value.
+    pub when: Option<(&'static str, &'static str)>,
+}
+
+impl DatasetFile {
+    /// Whether there is one file per season rather than a single file.
+    pub fn is_per_season(&self) -> bool {
+        self.path_template.contains("{season}")
+    }
+
+    /// Substitute `params` and `season` into the path template. Parameter
+    /// values appear in paths with `+` removed (`reg+post` => `regpost`).
+    pub fn path(&self, params: &[(&str, &str)], season: Option<i32>) -> String {
+        let mut path = self.path_template.to_string();
+        for (name, value) in params {
+            path = path.replace(&format!("{{{name}}}"), &value.replace('+', ""));
+        }
+        if let Some(season) = season {
+            path = path.replace("{season}", &season.to_string());
+        }
+        path
+    }
+
+    /// Whether `path`, a release path without extension, is an instance of
+    /// this file's template.
+    pub fn matches(&self, path: &str) -> bool {
+        let mut pieces = self.path_template.split('{');
+        let Some(mut rest) = path.strip_prefix(pieces.next().unwrap_or_default()) else {
+            return false;
+        };
+        let literals: Vec<&str> = pieces
+            .map(|p| p.split_once('}').map_or(p, |(_, literal)| literal))
+            .collect();
+        if literals.is_empty() {
+            return rest.is_empty();
+        }
+
+        // Every placeholder matches at least one character other than `/`.
+        for (i, literal) in literals.iter().enumerate() {
+            if i == literals.len() - 1 {
+                return rest.len() > literal.len()
+                    && rest.ends_with(literal)
+                    && !rest[..rest.len() - literal.len()].contains('/');
+            }
+            let Some(pos) = rest.get(1..).and_then(|r| r.find(literal)) else {
+                return false;
+            };
+            if rest[..=pos].contains('/') {
+                return false;
+            }
+            rest = &rest[1 + pos + literal.len()..];
+        }
+        unreachable!()
+    }
+
+    fn applies_to(&self, params: &[(&str, &str)]) -> bool {
+        match self.when {
+            None => true,
+            Some((name, value)) => params.iter().any(|(n, v)| *n == name && *v == value),
+        }
+    }
+}
+
+/// A dataset the crate knows how to load.
+#[derive(Debug, Clone, Copy)]
+pub struct Dataset {
+    /// Catalog name, matching the loader (`pbp` for `load_pbp`).
+    pub name: &'static str,
+    pub description: &'static str,
+    pub repository: Repository,
+    /// The release files; most datasets have exactly one, while datasets whose
+    /// layout depends on a parameter list one file per value.
+    pub files: &'static [DatasetFile],
+    /// The earliest season with data, for datasets that have seasons.
+    pub first_season: Option<i32>,
+    /// Whether the loader takes a `seasons` argument, either selecting
+    /// per-season files or filtering a single file by its `season` column.
+    pub accepts_seasons: bool,
+    pub season_cutoff: SeasonCutoff,
+    pub parameters: &'static [Parameter],
+}
+
+impl Dataset {
+    /// Whether the dataset is published as one file per season. For datasets
+    /// whose layout depends on a parameter, see [`Dataset::file`].
+    pub fn is_per_season(&self) -> bool {
+        self.files.iter().all(DatasetFile::is_per_season)
+    }
+
+    /// The current season according to this dataset's cutoff date.
+    pub fn current_season(&self) -> i32 {
+        get_current_season(self.season_cutoff == SeasonCutoff::Roster)
+    }
+
+    pub fn parameter(&self, name: &str) -> Option<&'static Parameter> {
+        self.parameters.iter().find(|p| p.name == name)
+    }
+
+    /// Check that `value` is valid for parameter `name`.
+    pub fn validate_param(&self, name: &str, value: &str) -> Result<()> {
+        let Some(param) = self.parameter(name) else {
+            return Err(NflReadError::InvalidParameter(format!(
+                "Unknown parameter '{name}' for {}. Valid parameters: {}",
+                self.name,
+                self.parameter_names()
+            )));
+        };
+        if param.values.contains(&value) {
+            Ok(())
+        } else {
+            Err(NflReadError::InvalidParameter(format!(
+                "Invalid {name}: '{value}'. Must be one of: {}",
+                param.values.join(", ")
+            )))
+        }
+    }
+
+    /// Check every `(name, value)` pair against the dataset's parameters.
+    pub fn validate_params(&self, params: &[(&str, &str)]) -> Result<()> {
+        for (name, value) in params {
+            self.validate_param(name, value)?;
+        }
+        Ok(())
+    }
+
+    /// Check that no season is before the dataset's first season.
+    pub fn validate_seasons(&self, seasons: &[i32]) -> Result<()> {
+        let Some(first) = self.first_season else {
+            return Ok(());
+        };
+        match seasons.iter().find(|s| **s < first) {
+            Some(s) => Err(NflReadError::InvalidSeason(format!(
+                "Season {s} is before first available season ({first})"
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// The release file used for the given parameter values.
+    pub fn file(&self, params: &[(&str, &str)]) -> Result<&'static DatasetFile> {
+        self.validate_params(params)?;
+        self.files
+            .iter()
+            .find(|f| f.applies_to(params))
+            .ok_or_else(|| {
+                NflReadError::InvalidParameter(format!(
+                    "Missing parameter for {}. Required: {}",
+                    self.name,
+                    self.parameter_names()
+                ))
+            })
+    }
+
+    /// Release paths for the given parameter values: one per season for
+    /// per-season files, or the single file (ignoring `seasons`) otherwise.
+    pub fn paths(&self, params: &[(&str, &str)], seasons: &[i32]) -> Result<Vec<String>> {
+        let file = self.file(params)?;
+        if file.is_per_season() {
+            self.validate_seasons(seasons)?;
+            Ok(seasons
+                .iter()
+                .map(|season| file.path(params, Some(*season)))
+                .collect())
+        } else {
+            Ok(vec![file.path(params, None)])
+        }
+    }
+
+    /// Release path of a single-file dataset for the given parameter values.
+    pub fn path(&self, params: &[(&str, &str)]) -> Result<String> {
+        Ok(self.file(params)?.path(params, None))
+    }
+
+    /// The season years `seasons` selects for this dataset. See [`Seasons`]
+    /// for the rules.
+    pub fn resolve_seasons(&self, seasons: &Seasons) -> Result<Vec<i32>> {
+        seasons.resolve(self.first_season, self.current_season())
+    }
+
+    /// The seasons to keep when filtering a single file by its `season`
+    /// column, or None for [`Seasons::All`], which keeps every row.
+    pub(crate) fn season_filter(&self, seasons: &Seasons) -> Result<Option<Vec<i32>>> {
+        match seasons {
+            Seasons::All => Ok(None),
+            seasons => self.resolve_seasons(seasons).map(Some),
+        }
+    }
+
+    /// Like [`Dataset::paths`], but for a [`Seasons`] selection. Seasons given
+    /// for a single-file dataset are only validated.
+    pub(crate) fn resolve_paths(
+        &self,
+        params: &[(&str, &str)],
+        seasons: &Seasons,
+    ) -> Result<Vec<String>> {
+        let file = self.file(params)?;
+        if !file.is_per_season() {
+            self.season_filter(seasons)?;
+            return Ok(vec![file.path(params, None)]);
+        }
+        Ok(self
+            .resolve_seasons(seasons)?
+            .iter()
+            .map(|season| file.path(params, Some(*season)))
+            .collect())
+    }
+
+    /// Whether `url` points at one of this dataset's files, under the
+    /// repository's default base URL or the configured or mirror base URLs of
+    /// `config`.
+    pub fn matches_url(&self, url: &str, config: &Config) -> bool {
+        self.repository
+            .release_path(url, config)
+            .is_some_and(|path| {
+                let path = path.rsplit_once('.').map_or(path, |(stem, _)| stem);
+                self.files.iter().any(|file| file.matches(path))
+            })
+    }
+
+    /// Every combination of parameter values, as `(name, value)` pairs.
+    pub fn parameter_combinations(&self) -> Vec<Vec<(&'static str, &'static str)>> {
+        let mut combinations = vec![Vec::new()];
+        for param in self.parameters {
+            combinations = combinations
+                .into_iter()
+                .flat_map(|combo| {
+                    param.values.iter().map(move |value| {
+                        let mut next = combo.clone();
+                        next.push((param.name, *value));
+                        next
+                    })
+                })
+                .collect();
+        }
+        combinations
+    }
+
+    /// Every release file of the dataset across all parameter values and all
+    /// seasons from `first_season` to the current one, without duplicates.
+    pub fn all_files(&self) -> Vec<(String, DataFormat)> {
+        let current = self.current_season();
+        let mut files: Vec<(String, DataFormat)> = Vec::new();
+        for params in self.parameter_combinations() {
+            for file in self.files.iter().filter(|f| f.applies_to(&params)) {
+                let seasons: Vec<Option<i32>> = match (file.is_per_season(), self.first_season) {
+                    (true, Some(first)) => (first..=current).map(Some).collect(),
+                    _ => vec![None],
+                };
+                for season in seasons {
+                    let entry = (file.path(&params, season), file.format);
+                    if !files.contains(&entry) {
+                        files.push(entry);
+                    }
+                }
+            }
+        }
+        files
+    }
+
+    fn parameter_names(&self) -> String {
+        if self.parameters.is_empty() {
+            return "none".to_string();
+        }
+        self.parameters
+            .iter()
+            .map(|p| p.name)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Every dataset the crate can load.
+pub fn datasets() -> &'static [Dataset] {
+    DATASETS
+}
+
+/// Look up a dataset by its catalog name.
+pub fn find_dataset(name: &str) -> Option<&'static Dataset> {
+    DATASETS.iter().find(|d| d.name == name)
+}
+
+/// Look up a dataset by its catalog name, failing with `InvalidParameter`
+/// listing the valid names when there is none.
+pub fn lookup_dataset(name: &str) -> Result<&'static Dataset> {
+    find_dataset(name).ok_or_else(|| {
+        let names: Vec<&str> = DATASETS.iter().map(|d| d.name).collect();
+        NflReadError::InvalidParameter(format!(
+            "Unknown dataset: '{name}'. Must be one of: {}",
+            names.join(", ")
+        ))
+    })
+}
+
+/// Find the dataset a download URL belongs to.
+pub fn find_dataset_by_url(url: &str, config: &Config) -> Option<&'static Dataset> {
+    DATASETS.iter().find(|d| d.matches_url(url, config))
+}
+
+const fn parquet(path_template: &'static str) -> DatasetFile {
+    DatasetFile {
+        path_template,
+        format: DataFormat::Parquet,
+        when: None,
+    }
+}
+
+const fn parquet_when(
+    path_template: &'static str,
+    param: &'static str,
+    value: &'static str,
+) -> DatasetFile {
+    DatasetFile {
+        path_template,
+        format: DataFormat::Parquet,
+        when: Some((param, value)),
+    }
+}
+
+const fn nflverse(
+    name: &'static str,
+    description: &'static str,
+    files: &'static [DatasetFile],
+    first_season: Option<i32>,
+) -> Dataset {
+    Dataset {
+        name,
+        description,
+        repository: Repository::NflverseData,
+        files,
+        first_season,
+        accepts_seasons: true,
+        season_cutoff: SeasonCutoff::Standard,
+        parameters: &[],
+    }
+}
+
+const SUMMARY_LEVEL: Parameter = Parameter {
+    name: "summary_level",
+    values: &["week", "reg", "post", "reg+post"],
+    default: "week",
+};
+
+pub const PBP: Dataset = nflverse(
+    "pbp",
+    "Play-by-play data",
+    &[parquet("pbp/play_by_play_{season}")],
+    Some(1999),
+);
+
+pub const PARTICIPATION: Dataset = nflverse(
+    "participation",
+    "Play participation data",
+    &[parquet("pbp_participation/pbp_participation_{season}")],
+    Some(2016),
+);
+
+pub const SCHEDULES: Dataset = nflverse(
+    "schedules",
+    "Game schedules and results",
+    &[parquet("schedules/games")],
+    None,
+);
+
+pub const PLAYER_STATS: Dataset = Dataset {
+    parameters: &[SUMMARY_LEVEL],
+    ..nflverse(
+        "player_stats",
+        "Player stats",
+        &[parquet(
+            "stats_player/stats_player_{summary_level}_{season}",
+        )],
+        Some(1999),
+    )
+};
+
+pub const TEAM_STATS: Dataset = Dataset {
+    parameters: &[SUMMARY_LEVEL],
+    ..nflverse(
+        "team_stats",
+        "Team stats",
+        &[parquet("stats_team/stats_team_{summary_level}_{season}")],
+        Some(1999),
+    )
+};
+
+pub const NEXTGEN_STATS: Dataset = Dataset {
+    parameters: &[Parameter {
+        name: "stat_type",
+        values: &["passing", "receiving", "rushing"],
+        default: "passing",
+    }],
+    ..nflverse(
+        "nextgen_stats",
+        "Next Gen Stats",
+        &[parquet("nextgen_stats/ngs_{stat_type}")],
+        Some(2016),
+    )
+};
+
+pub const PFR_ADVSTATS: Dataset = Dataset {
+    parameters: &[
+        Parameter {
+            name: "stat_type",
+            values: &["pass", "rush", "rec", "def"],
+            default: "pass",
+        },
+        Parameter {
+            name: "summary_level",
+            values: &["week", "season"],
+            default: "week",
+        },
+    ],
+    ..nflverse(
+        "pfr_advstats",
+        "Pro Football Reference advanced stats",
+        &[
+            parquet_when(
+                "pfr_advstats/advstats_week_{stat_type}_{season}",
+                "summary_level",
+                "week",
+            ),
+            parquet_when(
+                "pfr_advstats/advstats_season_{stat_type}",
+                "summary_level",
+                "season",
+            ),
+        ],
+        Some(2018),
+    )
+};
+
+pub const SNAP_COUNTS: Dataset = nflverse(
+    "snap_counts",
+    "Snap counts",
+    &[parquet("snap_counts/snap_counts_{season}")],
+    Some(2012),
+);
+
+pub const ROSTERS: Dataset = Dataset {
+    season_cutoff: SeasonCutoff::Roster,
+    ..nflverse(
+        "rosters",
+        "Season rosters",
+        &[parquet("rosters/roster_{season}")],
+        Some(1920),
+    )
+};
+
+pub const ROSTERS_WEEKLY: Dataset = nflverse(
+    "rosters_weekly",
+    "Weekly rosters",
+    &[parquet("weekly_rosters/roster_weekly_{season}")],
+    Some(2002),
+);
+
+pub const PLAYERS: Dataset = Dataset {
+    accepts_seasons: false,
+    ..nflverse(
+        "players",
+        "Player biographical information",
+        &[parquet("players/players")],
+        None,
+    )
+};
+
+pub const DEPTH_CHARTS: Dataset = Dataset {
+    season_cutoff: SeasonCutoff::Roster,
+    ..nflverse(
+        "depth_charts",
+        "Depth charts",
+        &[parquet("depth_charts/depth_charts_{season}")],
+        Some(2001),
+    )
+};
+
+pub const INJURIES: Dataset = nflverse(
+    "injuries",
+    "Injury reports",
+    &[parquet("injuries/injuries_{season}")],
+    Some(2009),
+);
+
+pub const OFFICIALS: Dataset = nflverse(
+    "officials",
+    "Game officials",
+    &[parquet("officials/officials")],
+    None,
+);
+
+pub const DRAFT_PICKS: Dataset = nflverse(
+    "draft_picks",
+    "Draft picks",
+    &[parquet("draft_picks/draft_picks")],
+    None,
+);
+
+pub const COMBINE: Dataset = nflverse(
+    "combine",
+    "NFL combine results",
+    &[parquet("combine/combine")],
+    None,
+);
+
+pub const CONTRACTS: Dataset = Dataset {
+    accepts_seasons: false,
+    ..nflverse(
+        "contracts",
+        "Historical contract data",
+        &[parquet("contracts/historical_contracts")],
+        None,
+    )
+};
+
+pub const TRADES: Dataset = Dataset {
+    accepts_seasons: false,
+    ..nflverse("trades", "Trade data", &[parquet("trades/trades")], None)
+};
+
+pub const TEAMS: Dataset = Dataset {
+    accepts_seasons: false,
+    ..nflverse(
+        "teams",
+        "Team metadata, colors, and logos",
+        &[parquet("teams/teams_colors_logos")],
+        None,
+    )
+};
+
+pub const FTN_CHARTING: Dataset = nflverse(
+    "ftn_charting",
+    "FTN charting data",
+    &[parquet("ftn_charting/ftn_charting_{season}")],
+    Some(2022),
+);
+
+pub const FF_PLAYERIDS: Dataset = Dataset {
+    name: "ff_playerids",
+    description: "Fantasy player ID mappings",
+    repository: Repository::Dynastyprocess,
+    files: &[DatasetFile {
+        path_template: "db_playerids.csv",
+        format: DataFormat::Csv,
+        when: None,
+    }],
+    first_season: None,
+    accepts_seasons: false,
+    season_cutoff: SeasonCutoff::Standard,
+    parameters: &[],
+};
+
+pub const FF_RANKINGS: Dataset = Dataset {
+    name: "ff_rankings",
+    description: "Fantasy rankings",
+    repository: Repository::Dynastyprocess,
+    files: &[
+        DatasetFile {
+            path_template: "db_fpecr_latest.csv",
+            format: DataFormat::Csv,
+            when: Some(("ranking_type", "draft")),
+        },
+        DatasetFile {
+            path_template: "fp_latest_weekly.csv",
+            format: DataFormat::Csv,
+            when: Some(("ranking_type", "week")),
+        },
+        parquet_when("db_fpecr.parquet", "ranking_type", "all"),
+    ],
+    first_season: None,
+    accepts_seasons: false,
+    season_cutoff: SeasonCutoff::Standard,
+    parameters: &[Parameter {
+        name: "ranking_type",
+        values: &["draft", "week", "all"],
+        default: "draft",
+    }],
+};
+
+pub const FF_OPPORTUNITY: Dataset = Dataset {
+    name: "ff_opportunity",
+    description: "Fantasy opportunity data",
+    repository: Repository::Ffopportunity,
+    files: &[parquet("{model_version}-data/ep_{stat_type}_{season}")],
+    first_season: Some(2006),
+    accepts_seasons: true,
+    season_cutoff: SeasonCutoff::Standard,
+    parameters: &[
+        Parameter {
+            name: "stat_type",
+            values: &["weekly", "pbp_pass", "pbp_rush"],
+            default: "weekly",
+        },
+        Parameter {
+            name: "model_version",
+            values: &["latest", "v1.0.0"],
+            default: "latest",
+        },
+    ],
+};
+
+pub(crate) static DATASETS: &[Dataset] = &[
+    PBP,
+    PARTICIPATION,
+    SCHEDULES,
+    PLAYER_STATS,
+    TEAM_STATS,
+    NEXTGEN_STATS,
+    PFR_ADVSTATS,
+    SNAP_COUNTS,
+    ROSTERS,
+    ROSTERS_WEEKLY,
+    PLAYERS,
+    DEPTH_CHARTS,
+    INJURIES,
+    OFFICIALS,
+    DRAFT_PICKS,
+    COMBINE,
+    CONTRACTS,
+    TRADES,
+    TEAMS,
+    FTN_CHARTING,
+    FF_PLAYERIDS,
+    FF_RANKINGS,
+    FF_OPPORTUNITY,
+];
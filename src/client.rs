@@ -0,0 +1,102 @@
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use crate::config::{Config, get_config};
+use crate::error::Result;
+
+/// A shared HTTP client, either built from `Config` or supplied by the caller.
+enum ClientSlot<C> {
+    Managed {
+        client: C,
+        timeout: u64,
+        user_agent: String,
+    },
+    Injected(C),
+}
+
+static BLOCKING_CLIENT: OnceLock<Mutex<Option<ClientSlot<reqwest::blocking::Client>>>> =
+    OnceLock::new();
+static ASYNC_CLIENT: OnceLock<Mutex<Option<ClientSlot<reqwest::Client>>>> = OnceLock::new();
+
+/// Return the shared client from `slot`, (re)building it with `build` when
+/// there is none yet or when `timeout`/`user_agent` changed since it was built.
+fn shared_client<C: Clone>(
+    slot: &Mutex<Option<ClientSlot<C>>>,
+    config: &Config,
+    build: impl FnOnce(&Config) -> reqwest::Result<C>,
+) -> Result<C> {
+    let mut guard = slot.lock().unwrap();
+    match guard.as_ref() {
+        Some(ClientSlot::Injected(client)) => return Ok(client.clone()),
+        Some(ClientSlot::Managed {
+            client,
+            timeout,
+            user_agent,
+        }) if *timeout == config.timeout && *user_agent == config.user_agent => {
+            return Ok(client.clone());
+        }
+        _ => {}
+    }
+
+    let client = build(config)?;
+    *guard = Some(ClientSlot::Managed {
+        client: client.clone(),
+        timeout: config.timeout,
+        user_agent: config.user_agent.clone(),
+    });
+    Ok(client)
+}
+
+/// Get the shared blocking client used by the synchronous loaders.
+///
+/// The client is built lazily from the global `Config` and reused across
+/// downloads so connections and TLS sessions are pooled. It is rebuilt only
+/// when `timeout` or `user_agent` changes, unless one was injected with
+/// [`set_http_client`].
+pub fn http_client() -> Result<reqwest::blocking::Client> {
+    let slot = BLOCKING_CLIENT.get_or_init(|| Mutex::new(None));
+    shared_client(slot, &get_config(), |config| {
+        reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(config.timeout))
+            .user_agent(&config.user_agent)
+            .build()
+    })
+}
+
+/// Get the shared async client used by the `_async` loaders.
+/// See [`http_client`] for how it is built and reused.
+pub fn async_http_client() -> Result<reqwest::Client> {
+    let slot = ASYNC_CLIENT.get_or_init(|| Mutex::new(None));
+    shared_client(slot, &get_config(), |config| {
+        reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout))
+            .user_agent(&config.user_agent)
+            .build()
+    })
+}
+
+/// Use a preconfigured blocking client (proxies, custom root certificates, ...)
+/// for all synchronous downloads. `Config::timeout` and `Config::user_agent`
+/// are not applied to an injected client.
+pub fn set_http_client(client: reqwest::blocking::Client) {
+    let slot = BLOCKING_CLIENT.get_or_init(|| Mutex::new(None));
+    *slot.lock().unwrap() = Some(ClientSlot::Injected(client));
+}
+
+/// Use a preconfigured async client for all `_async` downloads.
+/// See [`set_http_client`].
+pub fn set_async_http_client(client: reqwest::Client) {
+    let slot = ASYNC_CLIENT.get_or_init(|| Mutex::new(None));
+    *slot.lock().unwrap() = Some(ClientSlot::Injected(client));
+}
+
+/// Drop any injected or cached clients so the next download builds fresh
+/// ones from the global `Config`.
+pub fn reset_http_clients() {
+    if let Some(slot) = BLOCKING_CLIENT.get() {
+        *slot.lock().unwrap() = None;
+    }
+    if let Some(slot) = ASYNC_CLIENT.get() {
+        *slot.lock().unwrap() = None;
+    }
+}
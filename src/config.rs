@@ -1,5 +1,11 @@
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
 use std::path::PathBuf;
 use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use crate::downloader::Repository;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CacheMode {
@@ -9,6 +15,7 @@ pub enum CacheMode {
 }
 
 impl CacheMode {
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Self {
         match s.to_lowercase().as_str() {
             "memory" => CacheMode::Memory,
@@ -19,14 +26,98 @@ impl CacheMode {
     }
 }
 
+/// How failed downloads are retried.
+///
+/// Connection errors, timeouts and the HTTP statuses in `retry_statuses` are
+/// retried with exponential backoff: attempt `n` waits `base_delay_ms * 2^(n-1)`
+/// milliseconds, capped at `max_delay_ms`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts per URL, including the first. `1` disables retries.
+    pub max_attempts: u32,
+    pub base_delay_ms: u64,
+    pub max_delay_ms: u64,
+    /// Randomize each delay to between half and all of its computed value.
+    pub jitter: bool,
+    pub retry_statuses: Vec<u16>,
+    /// Wait for the server's `Retry-After` header (capped at `max_delay_ms`)
+    /// instead of the computed backoff when it is present.
+    pub respect_retry_after: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay_ms: 500,
+            max_delay_ms: 30_000,
+            jitter: true,
+            retry_statuses: vec![408, 429, 500, 502, 503, 504],
+            respect_retry_after: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Whether a response with this HTTP status should be retried.
+    pub fn is_retriable_status(&self, status: u16) -> bool {
+        self.retry_statuses.contains(&status)
+    }
+
+    /// Backoff to wait after failed attempt number `attempt` (starting at 1).
+    pub fn backoff_delay(&self, attempt: u32) -> Duration {
+        let factor = 1u64 << attempt.saturating_sub(1).min(32);
+        let delay = self
+            .base_delay_ms
+            .saturating_mul(factor)
+            .min(self.max_delay_ms);
+        if self.jitter && delay > 0 {
+            let random = RandomState::new().build_hasher().finish();
+            Duration::from_millis(delay / 2 + random % (delay / 2 + 1))
+        } else {
+            Duration::from_millis(delay)
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub cache_mode: CacheMode,
     pub cache_dir: PathBuf,
     pub cache_duration: u64,
+    /// Approximate size budget in bytes for the in-memory cache. Least
+    /// recently used entries are evicted once it is exceeded, and a DataFrame
+    /// larger than the whole budget is not cached at all (with a `log`
+    /// warning). `0`, the default, disables the limit.
+    pub memory_cache_max_bytes: usize,
+    /// Size budget in bytes for the filesystem cache directory. Least
+    /// recently used entries are removed once a write exceeds it; `0`
+    /// disables the limit.
+    pub disk_cache_max_bytes: u64,
+    /// Store downloads in the filesystem cache exactly as received and parse
+    /// them on read, instead of re-encoding the parsed DataFrame as Parquet.
+    pub cache_raw: bool,
+    /// Fetch only the matching row groups of single-file datasets with HTTP
+    /// Range requests when loading a subset of seasons.
+    pub range_requests: bool,
     pub verbose: bool,
     pub timeout: u64,
     pub user_agent: String,
+    /// Maximum number of season files downloaded at once by multi-season loaders.
+    pub download_concurrency: usize,
+    pub retry: RetryPolicy,
+    /// Never touch the network: serve every load from the cache regardless of
+    /// its age, failing with `NflReadError::NotCached` on a miss.
+    pub offline: bool,
+    /// When a download fails, fall back to an expired cache entry (with a
+    /// warning) instead of returning the error.
+    pub stale_if_error: bool,
+    /// Per-repository replacements for [`Repository::default_base_url`],
+    /// e.g. an internal mirror or a local test server.
+    pub base_urls: HashMap<Repository, String>,
+    /// Per-repository fallback base URLs, tried in order when a download from
+    /// the primary base URL fails.
+    pub mirrors: HashMap<Repository, Vec<String>>,
 }
 
 impl Default for Config {
@@ -39,9 +130,19 @@ impl Default for Config {
             cache_mode: CacheMode::Memory,
             cache_dir,
             cache_duration: 86400,
+            memory_cache_max_bytes: 0,
+            disk_cache_max_bytes: 0,
+            cache_raw: false,
+            range_requests: true,
             verbose: false,
             timeout: 120,
             user_agent: format!("nflverse/nflreadrust {}", env!("CARGO_PKG_VERSION")),
+            download_concurrency: 4,
+            retry: RetryPolicy::default(),
+            offline: false,
+            stale_if_error: false,
+            base_urls: HashMap::new(),
+            mirrors: HashMap::new(),
         }
     }
 }
@@ -56,22 +157,80 @@ impl Config {
         if let Ok(val) = std::env::var("NFLREADRUST_CACHE_DIR") {
             config.cache_dir = PathBuf::from(val);
         }
-        if let Ok(val) = std::env::var("NFLREADRUST_CACHE_DURATION") {
-            if let Ok(n) = val.parse() {
-                config.cache_duration = n;
-            }
+        if let Ok(val) = std::env::var("NFLREADRUST_CACHE_DURATION")
+            && let Ok(n) = val.parse()
+        {
+            config.cache_duration = n;
+        }
+        if let Ok(val) = std::env::var("NFLREADRUST_MEMORY_CACHE_MAX_BYTES")
+            && let Ok(n) = val.parse()
+        {
+            config.memory_cache_max_bytes = n;
+        }
+        if let Ok(val) = std::env::var("NFLREADRUST_DISK_CACHE_MAX_BYTES")
+            && let Ok(n) = val.parse()
+        {
+            config.disk_cache_max_bytes = n;
+        }
+        if let Ok(val) = std::env::var("NFLREADRUST_CACHE_RAW") {
+            config.cache_raw = val == "1" || val.to_lowercase() == "true";
+        }
+        if let Ok(val) = std::env::var("NFLREADRUST_RANGE_REQUESTS") {
+            config.range_requests = val == "1" || val.to_lowercase() == "true";
         }
         if let Ok(val) = std::env::var("NFLREADRUST_VERBOSE") {
             config.verbose = val == "1" || val.to_lowercase() == "true";
         }
-        if let Ok(val) = std::env::var("NFLREADRUST_TIMEOUT") {
-            if let Ok(n) = val.parse() {
-                config.timeout = n;
-            }
+        if let Ok(val) = std::env::var("NFLREADRUST_OFFLINE") {
+            config.offline = val == "1" || val.to_lowercase() == "true";
+        }
+        if let Ok(val) = std::env::var("NFLREADRUST_STALE_IF_ERROR") {
+            config.stale_if_error = val == "1" || val.to_lowercase() == "true";
+        }
+        if let Ok(val) = std::env::var("NFLREADRUST_TIMEOUT")
+            && let Ok(n) = val.parse()
+        {
+            config.timeout = n;
         }
         if let Ok(val) = std::env::var("NFLREADRUST_USER_AGENT") {
             config.user_agent = val;
         }
+        if let Ok(val) = std::env::var("NFLREADRUST_DOWNLOAD_CONCURRENCY")
+            && let Ok(n) = val.parse()
+        {
+            config.download_concurrency = n;
+        }
+        if let Ok(val) = std::env::var("NFLREADRUST_RETRY_ATTEMPTS")
+            && let Ok(n) = val.parse()
+        {
+            config.retry.max_attempts = n;
+        }
+        if let Ok(val) = std::env::var("NFLREADRUST_RETRY_BASE_DELAY_MS")
+            && let Ok(n) = val.parse()
+        {
+            config.retry.base_delay_ms = n;
+        }
+        if let Ok(val) = std::env::var("NFLREADRUST_RETRY_STATUSES") {
+            config.retry.retry_statuses = val
+                .split(',')
+                .filter_map(|code| code.trim().parse().ok())
+                .collect();
+        }
+        for repo in Repository::ALL {
+            let prefix = repo.env_prefix();
+            if let Ok(val) = std::env::var(format!("{prefix}_URL")) {
+                config.base_urls.insert(repo, val);
+            }
+            if let Ok(val) = std::env::var(format!("{prefix}_MIRRORS")) {
+                let mirrors = val
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|m| !m.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                config.mirrors.insert(repo, mirrors);
+            }
+        }
 
         config
     }
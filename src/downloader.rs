@@ -1,18 +1,38 @@
+use bytes::Bytes;
+use futures_util::{StreamExt, TryStreamExt, stream};
+use polars::io::HiveOptions;
 use polars::prelude::*;
-use std::io::Cursor;
+use polars_plan::plans::ScanSources;
+use reqwest::StatusCode;
+use reqwest::header::{
+    ETAG, HeaderMap, HeaderValue, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, RETRY_AFTER,
+};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
-use crate::cache::{cache_get, cache_set, make_cache_key};
-use crate::config::get_config;
+use crate::cache::{
+    CacheMetadata, cache_file, cache_get, cache_get_stale, cache_refresh, cache_revalidate,
+    cache_set, cache_set_file, cache_set_raw, cache_validators, make_cache_key_in,
+};
+use crate::client::{async_http_client, http_client};
+use crate::config::{CacheMode, Config, get_config};
 use crate::error::{NflReadError, Result};
+use crate::ranged::{RangedRead, read_row_groups, read_row_groups_async};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum DataFormat {
     Parquet,
     Csv,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Repository {
     NflverseData,
     Espnscraper,
@@ -21,7 +41,36 @@ pub enum Repository {
 }
 
 impl Repository {
-    pub fn base_url(&self) -> &'static str {
+    pub const ALL: [Repository; 4] = [
+        Repository::NflverseData,
+        Repository::Espnscraper,
+        Repository::Dynastyprocess,
+        Repository::Ffopportunity,
+    ];
+
+    /// Short name of the repository, e.g. `nflverse-data`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Repository::NflverseData => "nflverse-data",
+            Repository::Espnscraper => "espnscrapeR-data",
+            Repository::Dynastyprocess => "dynastyprocess",
+            Repository::Ffopportunity => "ffopportunity",
+        }
+    }
+
+    /// Prefix of the environment variables overriding this repository's URLs,
+    /// e.g. `NFLREADRUST_NFLVERSE_DATA` for `NFLREADRUST_NFLVERSE_DATA_URL`.
+    pub fn env_prefix(&self) -> &'static str {
+        match self {
+            Repository::NflverseData => "NFLREADRUST_NFLVERSE_DATA",
+            Repository::Espnscraper => "NFLREADRUST_ESPNSCRAPER",
+            Repository::Dynastyprocess => "NFLREADRUST_DYNASTYPROCESS",
+            Repository::Ffopportunity => "NFLREADRUST_FFOPPORTUNITY",
+        }
+    }
+
+    /// The upstream GitHub URL that release paths are resolved against.
+    pub fn default_base_url(&self) -> &'static str {
         match self {
             Repository::NflverseData => {
                 "https://github.com/nflverse/nflverse-data/releases/download/"
@@ -37,6 +86,91 @@ impl Repository {
             }
         }
     }
+
+    /// The base URL in effect: the `Config::base_urls` override if set,
+    /// otherwise [`Repository::default_base_url`].
+    pub fn base_url(&self) -> String {
+        self.base_url_in(&get_config())
+    }
+
+    /// [`Repository::base_url`] under `config`.
+    pub fn base_url_in(&self, config: &Config) -> String {
+        match config.base_urls.get(self) {
+            Some(url) => normalize_base_url(url),
+            None => self.default_base_url().to_string(),
+        }
+    }
+
+    /// Fallback base URLs from `Config::mirrors`, in the order they are tried.
+    pub fn mirror_urls(&self) -> Vec<String> {
+        self.mirror_urls_in(&get_config())
+    }
+
+    /// [`Repository::mirror_urls`] under `config`.
+    pub fn mirror_urls_in(&self, config: &Config) -> Vec<String> {
+        config
+            .mirrors
+            .get(self)
+            .map(|mirrors| mirrors.iter().map(|m| normalize_base_url(m)).collect())
+            .unwrap_or_default()
+    }
+
+    /// The release path of `url` (with its extension) if it lies under this
+    /// repository's configured, default or mirror base URL in `config`.
+    pub fn release_path<'a>(&self, url: &'a str, config: &Config) -> Option<&'a str> {
+        let mut bases = vec![
+            self.base_url_in(config),
+            self.default_base_url().to_string(),
+        ];
+        bases.extend(self.mirror_urls_in(config));
+        bases
+            .iter()
+            .find_map(|base| url.strip_prefix(base.as_str()))
+    }
+
+    /// The repository `url` belongs to under `config`, along with its release
+    /// path.
+    pub fn from_url<'a>(url: &'a str, config: &Config) -> Option<(Repository, &'a str)> {
+        Repository::ALL
+            .into_iter()
+            .find_map(|repo| repo.release_path(url, config).map(|path| (repo, path)))
+    }
+}
+
+/// Ensure a base URL ends with `/`, turning a plain directory path into a
+/// `file://` URL so local release trees can be used as a data source.
+fn normalize_base_url(base: &str) -> String {
+    let url = if base.contains("://") {
+        base.to_string()
+    } else {
+        let dir = std::path::absolute(base).unwrap_or_else(|_| base.into());
+        reqwest::Url::from_directory_path(&dir)
+            .map(String::from)
+            .unwrap_or_else(|_| format!("file://{base}"))
+    };
+    if url.ends_with('/') {
+        url
+    } else {
+        format!("{url}/")
+    }
+}
+
+/// The filesystem path for a `file://` URL, or None for any other scheme.
+fn local_path(url: &str) -> Option<PathBuf> {
+    if !url.starts_with("file://") {
+        return None;
+    }
+    reqwest::Url::parse(url).ok()?.to_file_path().ok()
+}
+
+/// Read a file from a local data source, naming the path in any error.
+fn read_local(path: &Path) -> Result<Vec<u8>> {
+    std::fs::read(path).map_err(|e| {
+        NflReadError::Io(std::io::Error::new(
+            e.kind(),
+            format!("{}: {e}", path.display()),
+        ))
+    })
 }
 
 pub fn build_url(repo: Repository, path: &str, format: DataFormat) -> String {
@@ -53,41 +187,1056 @@ pub fn build_url(repo: Repository, path: &str, format: DataFormat) -> String {
 }
 
 pub fn download_dataframe(url: &str, format: DataFormat) -> Result<DataFrame> {
-    let cache_key = make_cache_key(url);
+    run_download(url, Target::Frame(format))?.into_frame()
+}
+
+/// Async version of [`download_dataframe`], built on the non-blocking
+/// `reqwest` client. Shares the same cache and configuration; cache reads and
+/// writes run on the blocking thread pool.
+pub async fn download_dataframe_async(url: &str, format: DataFormat) -> Result<DataFrame> {
+    let loaded = run_download_async(url, Target::Frame(format)).await?;
+    blocking(move || loaded.into_frame()).await
+}
+
+/// Drive a [`Download`] of `url` to completion, fetching with the blocking
+/// client.
+fn run_download(url: &str, target: Target) -> Result<Loaded> {
+    let mut step = Download::start(url, target);
+    loop {
+        match step {
+            Step::Done(result) => return result,
+            Step::Fetch(download) => {
+                let validators = download.validators.as_ref();
+                let fetched = fetch_with_mirrors(url, &download.config, validators);
+                step = download.finish(fetched);
+            }
+        }
+    }
+}
+
+/// Async version of [`run_download`].
+async fn run_download_async(url: &str, target: Target) -> Result<Loaded> {
+    let owned = url.to_string();
+    let mut step = blocking(move || Download::start(&owned, target)).await;
+    loop {
+        match step {
+            Step::Done(result) => return result,
+            Step::Fetch(download) => {
+                let validators = download.validators.as_ref();
+                let fetched = fetch_with_mirrors_async(url, &download.config, validators).await;
+                step = blocking(move || download.finish(fetched)).await;
+            }
+        }
+    }
+}
+
+/// Run blocking work (filesystem cache access, parsing) on tokio's blocking
+/// thread pool so it does not stall the async executor.
+pub(crate) async fn blocking<R, F>(f: F) -> R
+where
+    R: Send + 'static,
+    F: FnOnce() -> R + Send + 'static,
+{
+    let report = current_report();
+    match tokio::task::spawn_blocking(move || with_report(report, f)).await {
+        Ok(result) => result,
+        Err(e) => std::panic::resume_unwind(e.into_panic()),
+    }
+}
+
+/// What a [`Download`] produces.
+#[derive(Debug, Clone, Copy)]
+enum Target {
+    /// A DataFrame parsed from the body.
+    Frame(DataFormat),
+    /// A lazy scan of the Parquet file, which is kept on disk undecoded.
+    Scan,
+}
+
+/// The result of a [`Download`]. A scan may still yield a DataFrame when its
+/// data only exists in memory, e.g. a memory cache hit or a stale fallback.
+enum Loaded {
+    Frame(DataFrame),
+    Scan(Box<LazyFrame>),
+}
+
+impl Loaded {
+    fn scan(lf: LazyFrame) -> Self {
+        Loaded::Scan(Box::new(lf))
+    }
+
+    fn into_frame(self) -> Result<DataFrame> {
+        match self {
+            Loaded::Frame(df) => Ok(df),
+            Loaded::Scan(lf) => Ok(lf.collect()?),
+        }
+    }
+
+    fn into_lazy(self) -> LazyFrame {
+        match self {
+            Loaded::Frame(df) => df.lazy(),
+            Loaded::Scan(lf) => *lf,
+        }
+    }
+}
+
+/// The cache side of a [`download_dataframe`] or [`scan_parquet`] call,
+/// shared by the sync and async versions, which differ only in how they
+/// fetch.
+struct Download {
+    url: String,
+    target: Target,
+    cache_key: String,
+    config: Config,
+    /// Validators of an expired cache entry, making the fetch conditional.
+    validators: Option<CacheMetadata>,
+}
+
+/// The next step of a [`Download`].
+enum Step {
+    Done(Result<Loaded>),
+    Fetch(Box<Download>),
+}
+
+impl Download {
+    /// Serve `url` from a local source or the cache when possible, or else
+    /// prepare a (possibly conditional) fetch.
+    fn start(url: &str, target: Target) -> Step {
+        // Local data sources are read directly, bypassing the cache.
+        if let Some(path) = local_path(url) {
+            return Step::Done(match target {
+                Target::Frame(format) => read_local(&path)
+                    .and_then(|bytes| parse_dataframe(&bytes, format))
+                    .map(Loaded::Frame),
+                Target::Scan => {
+                    scan_sources(ScanSources::Paths(Arc::from([path]))).map(Loaded::scan)
+                }
+            });
+        }
+
+        let config = get_config();
+        let cache_key = make_cache_key_in(url, &config);
+        if let Some(result) = cached(&cache_key, target, &config) {
+            return Step::Done(result);
+        }
+
+        if config.offline {
+            return Step::Done(offline_lookup(url, &cache_key).map(Loaded::Frame));
+        }
+
+        let validators = cache_validators(&cache_key);
+        if config.verbose {
+            match validators {
+                Some(_) => eprintln!("Revalidating: {url}"),
+                None => eprintln!("Downloading: {url}"),
+            }
+        }
+        Step::Fetch(Box::new(Download {
+            url: url.to_string(),
+            target,
+            cache_key,
+            config,
+            validators,
+        }))
+    }
+
+    /// Handle the outcome of a fetch: cache a new body, refresh a revalidated
+    /// entry, or fall back to a stale one on failure.
+    fn finish(mut self: Box<Self>, fetched: Result<Fetched>) -> Step {
+        let fetched = match fetched {
+            Ok(fetched) => fetched,
+            Err(e) => {
+                let df = stale_fallback(&self.url, &self.cache_key, &self.config, e);
+                return Step::Done(df.map(Loaded::Frame));
+            }
+        };
+        match fetched {
+            Fetched::Body(bytes, metadata) => Step::Done(match self.target {
+                Target::Frame(format) => {
+                    store_body(&self.cache_key, &bytes, format, &metadata, &self.config)
+                        .map(Loaded::Frame)
+                }
+                Target::Scan => store_scan(&self.cache_key, &bytes, &metadata).map(Loaded::scan),
+            }),
+            Fetched::NotModified(metadata) => {
+                let revalidated = match self.target {
+                    Target::Frame(_) => {
+                        cache_revalidate(&self.cache_key, &metadata).map(|df| Ok(Loaded::Frame(df)))
+                    }
+                    Target::Scan => cache_refresh(&self.cache_key, &metadata)
+                        .then(|| cache_file(&self.cache_key))
+                        .flatten()
+                        .map(|file| scan_file(file).map(Loaded::scan)),
+                };
+                if let Some(result) = revalidated {
+                    return Step::Done(result);
+                }
+                // The cached copy could not be read back; fetch it unconditionally.
+                match self.validators.take() {
+                    Some(_) => Step::Fetch(self),
+                    None => Step::Done(Err(NflReadError::NoData)),
+                }
+            }
+        }
+    }
+}
+
+/// A fresh cache entry for `cache_key`. Scans open a filesystem entry in
+/// place rather than decoding it; an entry in the memory cache is already
+/// decoded.
+fn cached(cache_key: &str, target: Target, config: &Config) -> Option<Result<Loaded>> {
+    match target {
+        Target::Scan if config.cache_mode == CacheMode::Filesystem => {
+            cache_file(cache_key).map(|file| scan_file(file).map(Loaded::scan))
+        }
+        _ => cache_get(cache_key).map(|df| Ok(Loaded::Frame(df))),
+    }
+}
+
+/// Parse a downloaded body and cache it, verbatim when `Config::cache_raw` is set.
+fn store_body(
+    cache_key: &str,
+    bytes: &[u8],
+    format: DataFormat,
+    metadata: &CacheMetadata,
+    config: &Config,
+) -> Result<DataFrame> {
+    let df = parse_dataframe(bytes, format)?;
+    if config.cache_raw {
+        cache_set_raw(cache_key, bytes, format, &df, metadata);
+    } else {
+        cache_set(cache_key, &df, metadata);
+    }
+    Ok(df)
+}
+
+/// Keep a downloaded Parquet file on disk undecoded and scan it: as a
+/// filesystem cache entry, or outside filesystem mode as a temporary file
+/// that is removed once the scan is dropped.
+fn store_scan(cache_key: &str, bytes: &[u8], metadata: &CacheMetadata) -> Result<LazyFrame> {
+    let file = match cache_set_file(cache_key, bytes, metadata) {
+        Some(file) => file,
+        None => temp_file(bytes)?,
+    };
+    scan_file(file)
+}
+
+/// Write `bytes` to an anonymous temporary file. The file is unlinked as soon
+/// as it is written, so it disappears when the returned handle is closed.
+fn temp_file(bytes: &[u8]) -> Result<File> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let path = std::env::temp_dir().join(format!(
+        "nflreadrust-{}-{}.parquet",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let mut file = File::options()
+        .read(true)
+        .write(true)
+        .create_new(true)
+        .open(&path)?;
+    let written = file.write_all(bytes);
+    let _ = std::fs::remove_file(&path);
+    written?;
+    Ok(file)
+}
+
+/// Serve `url` from the cache of any age, as offline mode never downloads.
+fn offline_lookup(url: &str, cache_key: &str) -> Result<DataFrame> {
+    match cache_get_stale(cache_key) {
+        Some((df, _age)) => Ok(df),
+        None => Err(NflReadError::NotCached(url.to_string())),
+    }
+}
+
+/// Fall back to an expired cache entry after a failed download when
+/// `Config::stale_if_error` allows it; otherwise return the error.
+///
+/// Each fallback is reported as a `log` warning naming the URL and the age of
+/// the data served.
+fn stale_fallback(
+    url: &str,
+    cache_key: &str,
+    config: &Config,
+    error: NflReadError,
+) -> Result<DataFrame> {
+    if !config.stale_if_error {
+        return Err(error);
+    }
+    let Some((df, age_secs)) = cache_get_stale(cache_key) else {
+        return Err(error);
+    };
+
+    log::warn!("failed to download {url} ({error}); using cached copy from {age_secs}s ago");
+    if let Some(report) = current_report() {
+        let mut freshness = report.lock().unwrap();
+        freshness.stale = true;
+        freshness.age_secs = freshness.age_secs.max(age_secs);
+    }
+    Ok(df)
+}
+
+/// How fresh the data returned by a load was. See [`track_freshness`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Freshness {
+    /// Whether any of the data was served from an expired cache entry after
+    /// its download failed (see `Config::stale_if_error`).
+    pub stale: bool,
+    /// Age in seconds of the oldest expired entry served; zero when fresh.
+    pub age_secs: u64,
+}
+
+/// The report that stale fallbacks of the current call are recorded in.
+type FreshnessReport = Arc<Mutex<Freshness>>;
+
+thread_local! {
+    static THREAD_REPORT: RefCell<Option<FreshnessReport>> = const { RefCell::new(None) };
+}
+
+tokio::task_local! {
+    static TASK_REPORT: FreshnessReport;
+}
+
+/// Run `f`, typically a load, and report whether any of the data it loaded
+/// was served stale:
+///
+/// ```no_run
+/// let (df, freshness) = nflreadrust::track_freshness(|| nflreadrust::load_pbp(2023, None));
+/// if freshness.stale {
+///     eprintln!("play-by-play data is {}s old", freshness.age_secs);
+/// }
+/// ```
+///
+/// Only fallbacks made by `f` itself, including the downloads it runs
+/// concurrently, are counted; other threads' loads are not.
+pub fn track_freshness<R>(f: impl FnOnce() -> R) -> (R, Freshness) {
+    let report = FreshnessReport::default();
+    let result = with_report(Some(Arc::clone(&report)), f);
+    let freshness = *report.lock().unwrap();
+    (result, freshness)
+}
+
+/// Async version of [`track_freshness`].
+pub async fn track_freshness_async<F: Future>(future: F) -> (F::Output, Freshness) {
+    let report = FreshnessReport::default();
+    let result = TASK_REPORT.scope(Arc::clone(&report), future).await;
+    let freshness = *report.lock().unwrap();
+    (result, freshness)
+}
+
+/// The report of the [`track_freshness`] call being run, if any.
+fn current_report() -> Option<FreshnessReport> {
+    TASK_REPORT
+        .try_with(Arc::clone)
+        .ok()
+        .or_else(|| THREAD_REPORT.with(|report| report.borrow().clone()))
+}
+
+/// Run `f` with `report` as the current thread's report, so work handed to
+/// another thread is tracked by the call that started it.
+fn with_report<R>(report: Option<FreshnessReport>, f: impl FnOnce() -> R) -> R {
+    let previous = THREAD_REPORT.with(|current| current.replace(report));
+    let result = f();
+    THREAD_REPORT.with(|current| current.replace(previous));
+    result
+}
+
+/// The outcome of a (possibly conditional) download.
+enum Fetched {
+    Body(Bytes, CacheMetadata),
+    /// The cached copy is current; carries the 304 response's validators.
+    NotModified(CacheMetadata),
+}
+
+/// Fetch `url`, and if that fails, the same path from each configured mirror
+/// of its repository in turn. Returns the last error if every source fails.
+fn fetch_with_mirrors(
+    url: &str,
+    config: &Config,
+    validators: Option<&CacheMetadata>,
+) -> Result<Fetched> {
+    from_mirrors(url, config, |candidate| {
+        fetch_bytes(candidate, config, validators)
+    })
+    .map(|(_, fetched)| fetched)
+}
+
+/// Async version of [`fetch_with_mirrors`].
+async fn fetch_with_mirrors_async(
+    url: &str,
+    config: &Config,
+    validators: Option<&CacheMetadata>,
+) -> Result<Fetched> {
+    from_mirrors_async(url, config, async |candidate| {
+        fetch_bytes_async(candidate, config, validators).await
+    })
+    .await
+    .map(|(_, fetched)| fetched)
+}
+
+/// Call `fetch` with `url`, and if that fails, with the same path on each
+/// configured mirror of its repository in turn. Returns the URL that
+/// succeeded with its result, or the last error if every source fails.
+pub(crate) fn from_mirrors<T>(
+    url: &str,
+    config: &Config,
+    mut fetch: impl FnMut(&str) -> Result<T>,
+) -> Result<(String, T)> {
+    let candidates = candidate_urls(url, config);
+    let mut last_error = None;
+    for candidate in &candidates {
+        match fetch(candidate) {
+            Ok(fetched) => return Ok((candidate.clone(), fetched)),
+            Err(e) => {
+                if config.verbose && candidates.len() > 1 {
+                    eprintln!("Failed to download {candidate}: {e}");
+                }
+                last_error = Some(e);
+            }
+        }
+    }
+    Err(last_error.expect("at least one candidate URL"))
+}
+
+/// Async version of [`from_mirrors`].
+pub(crate) async fn from_mirrors_async<T>(
+    url: &str,
+    config: &Config,
+    mut fetch: impl AsyncFnMut(&str) -> Result<T>,
+) -> Result<(String, T)> {
+    let candidates = candidate_urls(url, config);
+    let mut last_error = None;
+    for candidate in &candidates {
+        match fetch(candidate).await {
+            Ok(fetched) => return Ok((candidate.clone(), fetched)),
+            Err(e) => {
+                if config.verbose && candidates.len() > 1 {
+                    eprintln!("Failed to download {candidate}: {e}");
+                }
+                last_error = Some(e);
+            }
+        }
+    }
+    Err(last_error.expect("at least one candidate URL"))
+}
+
+/// `url` followed by the equivalent URL on each mirror of the repository
+/// whose base URL it starts with.
+fn candidate_urls(url: &str, config: &Config) -> Vec<String> {
+    let mut candidates = vec![url.to_string()];
+    for repo in Repository::ALL {
+        if let Some(path) = url.strip_prefix(&repo.base_url_in(config)) {
+            let mirrors = repo.mirror_urls_in(config);
+            candidates.extend(mirrors.iter().map(|mirror| format!("{mirror}{path}")));
+            break;
+        }
+    }
+    candidates
+}
+
+/// Fetch the body at `url`, retrying transient failures per `Config::retry`.
+///
+/// When `validators` are given the request is conditional, and an unchanged
+/// resource yields [`Fetched::NotModified`] instead of a body.
+fn fetch_bytes(url: &str, config: &Config, validators: Option<&CacheMetadata>) -> Result<Fetched> {
+    if let Some(path) = local_path(url) {
+        let metadata = CacheMetadata {
+            url: url.to_string(),
+            ..CacheMetadata::default()
+        };
+        return Ok(Fetched::Body(Bytes::from(read_local(&path)?), metadata));
+    }
 
-    if let Some(df) = cache_get(&cache_key) {
+    let (status, headers, bytes) = get_with_retry(url, config, conditional_headers(validators))?;
+    let metadata = response_metadata(url, &headers);
+    if status == StatusCode::NOT_MODIFIED {
+        return Ok(Fetched::NotModified(metadata));
+    }
+    Ok(Fetched::Body(bytes, metadata))
+}
+
+/// GET `url` with `headers`, retrying transient failures per `Config::retry`.
+/// Returns the status, headers and body of the first successful response.
+pub(crate) fn get_with_retry(
+    url: &str,
+    config: &Config,
+    headers: HeaderMap,
+) -> Result<(StatusCode, HeaderMap, Bytes)> {
+    let client = http_client()?;
+    let mut attempt = 1;
+    loop {
+        let request = client.get(url).headers(headers.clone());
+        let failure = match request.send() {
+            Ok(response) => {
+                let retry_after = retry_after(response.headers());
+                match response.error_for_status() {
+                    Ok(response) => {
+                        let status = response.status();
+                        let headers = response.headers().clone();
+                        match response.bytes() {
+                            Ok(bytes) => return Ok((status, headers, bytes)),
+                            Err(e) => (e, None),
+                        }
+                    }
+                    Err(e) => (e, retry_after),
+                }
+            }
+            Err(e) => (e, None),
+        };
+        std::thread::sleep(retry_or_fail(config, url, attempt, failure)?);
+        attempt += 1;
+    }
+}
+
+/// Async version of [`fetch_bytes`].
+async fn fetch_bytes_async(
+    url: &str,
+    config: &Config,
+    validators: Option<&CacheMetadata>,
+) -> Result<Fetched> {
+    if let Some(path) = local_path(url) {
+        let metadata = CacheMetadata {
+            url: url.to_string(),
+            ..CacheMetadata::default()
+        };
+        let bytes = blocking(move || read_local(&path)).await?;
+        return Ok(Fetched::Body(Bytes::from(bytes), metadata));
+    }
+
+    let (status, headers, bytes) =
+        get_with_retry_async(url, config, conditional_headers(validators)).await?;
+    let metadata = response_metadata(url, &headers);
+    if status == StatusCode::NOT_MODIFIED {
+        return Ok(Fetched::NotModified(metadata));
+    }
+    Ok(Fetched::Body(bytes, metadata))
+}
+
+/// Async version of [`get_with_retry`].
+pub(crate) async fn get_with_retry_async(
+    url: &str,
+    config: &Config,
+    headers: HeaderMap,
+) -> Result<(StatusCode, HeaderMap, Bytes)> {
+    let client = async_http_client()?;
+    let mut attempt = 1;
+    loop {
+        let request = client.get(url).headers(headers.clone());
+        let failure = match request.send().await {
+            Ok(response) => {
+                let retry_after = retry_after(response.headers());
+                match response.error_for_status() {
+                    Ok(response) => {
+                        let status = response.status();
+                        let headers = response.headers().clone();
+                        match response.bytes().await {
+                            Ok(bytes) => return Ok((status, headers, bytes)),
+                            Err(e) => (e, None),
+                        }
+                    }
+                    Err(e) => (e, retry_after),
+                }
+            }
+            Err(e) => (e, None),
+        };
+        tokio::time::sleep(retry_or_fail(config, url, attempt, failure)?).await;
+        attempt += 1;
+    }
+}
+
+/// Decide what to do after failed attempt number `attempt`: either the delay
+/// to wait before trying again, or the error to give up with.
+fn retry_or_fail(
+    config: &Config,
+    url: &str,
+    attempt: u32,
+    (error, retry_after): (reqwest::Error, Option<Duration>),
+) -> Result<Duration> {
+    let policy = &config.retry;
+    let retriable = match error.status() {
+        Some(status) => policy.is_retriable_status(status.as_u16()),
+        None => error.is_connect() || error.is_timeout() || error.is_request() || error.is_body(),
+    };
+    if !retriable || attempt >= policy.max_attempts {
+        return Err(NflReadError::Http(error));
+    }
+
+    let delay = match retry_after {
+        Some(delay) if policy.respect_retry_after => {
+            delay.min(Duration::from_millis(policy.max_delay_ms))
+        }
+        _ => policy.backoff_delay(attempt),
+    };
+    if config.verbose {
+        eprintln!(
+            "Retrying {url} in {}ms (attempt {}/{}): {error}",
+            delay.as_millis(),
+            attempt + 1,
+            policy.max_attempts
+        );
+    }
+    Ok(delay)
+}
+
+/// `If-None-Match` / `If-Modified-Since` headers for a conditional request.
+fn conditional_headers(validators: Option<&CacheMetadata>) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    let Some(validators) = validators else {
+        return headers;
+    };
+    if let Some(etag) = validators
+        .etag
+        .as_deref()
+        .and_then(|v| HeaderValue::from_str(v).ok())
+    {
+        headers.insert(IF_NONE_MATCH, etag);
+    }
+    if let Some(date) = validators
+        .last_modified
+        .as_deref()
+        .and_then(|v| HeaderValue::from_str(v).ok())
+    {
+        headers.insert(IF_MODIFIED_SINCE, date);
+    }
+    headers
+}
+
+/// Cache metadata for a successful response, keeping its validators.
+pub(crate) fn response_metadata(url: &str, headers: &HeaderMap) -> CacheMetadata {
+    let header = |name| {
+        headers
+            .get(name)
+            .and_then(|v: &HeaderValue| v.to_str().ok())
+            .map(str::to_string)
+    };
+    CacheMetadata {
+        url: url.to_string(),
+        etag: header(ETAG),
+        last_modified: header(LAST_MODIFIED),
+        ..CacheMetadata::default()
+    }
+}
+
+/// Parse a `Retry-After` header given either as seconds or as an HTTP date.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    (date.with_timezone(&chrono::Utc) - chrono::Utc::now())
+        .to_std()
+        .ok()
+}
+
+/// Download every URL, returning one DataFrame per URL in the same order.
+///
+/// Up to `Config::download_concurrency` files are fetched at once. If any
+/// download fails, no further downloads are started and the error for the
+/// earliest failing URL is returned.
+pub fn download_all(urls: &[String], format: DataFormat) -> Result<Vec<DataFrame>> {
+    let workers = get_config().download_concurrency;
+    let results = run_concurrently(urls, workers, true, |url| download_dataframe(url, format));
+    first_error(urls, results)
+}
+
+/// The results of [`run_concurrently`] over `urls` in order, or the error of
+/// the earliest URL that failed or was never started.
+fn first_error<R>(urls: &[String], results: Vec<Option<Result<R>>>) -> Result<Vec<R>> {
+    urls.iter()
+        .zip(results)
+        .map(|(url, result)| {
+            result.unwrap_or_else(|| {
+                Err(NflReadError::Io(std::io::Error::other(format!(
+                    "download of {url} was not started"
+                ))))
+            })
+        })
+        .collect()
+}
+
+/// Run `f` over `items` on up to `workers` threads, returning the results in
+/// input order. With `stop_on_error`, no new items are started once one has
+/// failed, and the items never started are returned as None.
+pub(crate) fn run_concurrently<T, R, F>(
+    items: &[T],
+    workers: usize,
+    stop_on_error: bool,
+    f: F,
+) -> Vec<Option<Result<R>>>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> Result<R> + Sync,
+{
+    let workers = workers.clamp(1, items.len().max(1));
+    let next = AtomicUsize::new(0);
+    let failed = AtomicBool::new(false);
+    let results: Mutex<Vec<Option<Result<R>>>> = Mutex::new(items.iter().map(|_| None).collect());
+    let report = current_report();
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                with_report(report.clone(), || {
+                    while !(stop_on_error && failed.load(Ordering::Relaxed)) {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(i) else { break };
+                        let result = f(item);
+                        if result.is_err() {
+                            failed.store(true, Ordering::Relaxed);
+                        }
+                        results.lock().unwrap()[i] = Some(result);
+                    }
+                })
+            });
+        }
+    });
+
+    results.into_inner().unwrap()
+}
+
+/// Async version of [`download_all`]. Downloads run concurrently on the
+/// calling task, bounded by `Config::download_concurrency`.
+pub async fn download_all_async(urls: &[String], format: DataFormat) -> Result<Vec<DataFrame>> {
+    let workers = get_config().download_concurrency.max(1);
+    stream::iter(urls)
+        .map(|url| download_dataframe_async(url, format))
+        .buffered(workers)
+        .try_collect()
+        .await
+}
+
+/// Download the Parquet file at `url`, reading only those of `columns` that
+/// it has, in the given order.
+///
+/// The file goes through [`scan_parquet`], so the other columns are never
+/// decoded and no full-width frame is kept: the filesystem cache stores the
+/// file verbatim, and the memory cache holds only the projection, under a
+/// key naming the columns. A frame already in the memory cache in full is
+/// projected as it is.
+pub fn download_parquet_columns(url: &str, columns: &[&str]) -> Result<DataFrame> {
+    let key = projection_cache_key(url, columns);
+    if let Some(df) = key.as_deref().and_then(cache_get) {
         return Ok(df);
     }
+    let df = read_columns(scan_parquet(url)?, columns)?;
+    if let Some(key) = key {
+        cache_set(&key, &df, &partial_metadata(url));
+    }
+    Ok(df)
+}
+
+/// Async version of [`download_parquet_columns`].
+pub async fn download_parquet_columns_async(url: &str, columns: &[&str]) -> Result<DataFrame> {
+    let key = projection_cache_key(url, columns);
+    if let Some(key) = key.clone()
+        && let Some(df) = blocking(move || cache_get(&key)).await
+    {
+        return Ok(df);
+    }
+    let lf = scan_parquet_async(url).await?;
+    let columns: Vec<String> = columns.iter().map(|c| c.to_string()).collect();
+    let url = url.to_string();
+    blocking(move || {
+        let columns: Vec<&str> = columns.iter().map(String::as_str).collect();
+        let df = read_columns(lf, &columns)?;
+        if let Some(key) = key {
+            cache_set(&key, &df, &partial_metadata(&url));
+        }
+        Ok(df)
+    })
+    .await
+}
 
+/// Download several Parquet URLs with [`download_parquet_columns`],
+/// concurrently and in order, as [`download_all`] does.
+pub fn download_all_columns(urls: &[String], columns: &[&str]) -> Result<Vec<DataFrame>> {
+    let workers = get_config().download_concurrency;
+    let results = run_concurrently(urls, workers, true, |url| {
+        download_parquet_columns(url, columns)
+    });
+    first_error(urls, results)
+}
+
+/// Async version of [`download_all_columns`].
+pub async fn download_all_columns_async(
+    urls: &[String],
+    columns: &[&str],
+) -> Result<Vec<DataFrame>> {
+    let workers = get_config().download_concurrency.max(1);
+    stream::iter(urls)
+        .map(|url| download_parquet_columns_async(url, columns))
+        .buffered(workers)
+        .try_collect()
+        .await
+}
+
+/// The memory cache key of a projection of `url`, or None outside memory
+/// mode, where the projection is not cached separately.
+fn projection_cache_key(url: &str, columns: &[&str]) -> Option<String> {
     let config = get_config();
+    (config.cache_mode == CacheMode::Memory)
+        .then(|| columns_cache_key(&make_cache_key_in(url, &config), columns))
+}
 
-    if config.verbose {
-        eprintln!("Downloading: {url}");
+/// Select those of `columns` that `lf` has and collect them. When it has
+/// none of them, its first column is kept so the row count survives.
+fn read_columns(mut lf: LazyFrame, columns: &[&str]) -> Result<DataFrame> {
+    let schema = lf.collect_schema()?;
+    let mut present: Vec<Expr> = columns
+        .iter()
+        .filter(|name| schema.contains(name))
+        .map(|name| col(*name))
+        .collect();
+    if present.is_empty()
+        && let Some(first) = schema.iter_names().next()
+    {
+        present.push(col(first.clone()));
     }
+    Ok(lf.select(present).collect()?)
+}
+
+/// Scan the Parquet file at `url` lazily, so column selections and filters
+/// are pushed down into the read.
+///
+/// The file is scanned from disk without being decoded up front: local files
+/// and fresh filesystem cache entries in place, and a download from the new
+/// filesystem cache entry or, in other cache modes, from a temporary file.
+/// Data that is only available decoded, i.e. a memory cache hit or the
+/// expired entry served offline or by `Config::stale_if_error`, is wrapped
+/// as it is. Scans do not add to the memory cache.
+pub fn scan_parquet(url: &str) -> Result<LazyFrame> {
+    Ok(run_download(url, Target::Scan)?.into_lazy())
+}
 
-    let client = reqwest::blocking::Client::builder()
-        .timeout(Duration::from_secs(config.timeout))
-        .user_agent(&config.user_agent)
-        .build()?;
+/// Async version of [`scan_parquet`].
+pub async fn scan_parquet_async(url: &str) -> Result<LazyFrame> {
+    Ok(run_download_async(url, Target::Scan).await?.into_lazy())
+}
 
-    let response = client.get(url).send()?;
+/// Scan an open Parquet file, such as a filesystem cache entry, which
+/// eviction cannot pull from under the scan.
+fn scan_file(file: File) -> Result<LazyFrame> {
+    scan_sources(ScanSources::Files(Arc::from([file])))
+}
 
-    if !response.status().is_success() {
-        return Err(NflReadError::Http(
-            response
-                .error_for_status()
-                .expect_err("Expected error status"),
-        ));
+fn scan_sources(sources: ScanSources) -> Result<LazyFrame> {
+    let args = ScanArgsParquet {
+        // Hive partitions are read from paths, which an open file has none of.
+        hive_options: HiveOptions {
+            enabled: Some(false),
+            ..HiveOptions::default()
+        },
+        ..ScanArgsParquet::default()
+    };
+    Ok(LazyFrame::scan_parquet_sources(sources, args)?)
+}
+
+/// Scan several Parquet URLs with [`scan_parquet`], preserving their order.
+/// Up to `download_concurrency` files are downloaded at once.
+pub fn scan_all(urls: &[String]) -> Result<Vec<LazyFrame>> {
+    let workers = get_config().download_concurrency;
+    first_error(
+        urls,
+        run_concurrently(urls, workers, true, |url| scan_parquet(url)),
+    )
+}
+
+/// Async version of [`scan_all`].
+pub async fn scan_all_async(urls: &[String]) -> Result<Vec<LazyFrame>> {
+    let workers = get_config().download_concurrency.max(1);
+    stream::iter(urls)
+        .map(|url| scan_parquet_async(url))
+        .buffered(workers)
+        .try_collect()
+        .await
+}
+
+/// Download the part of the single Parquet file at `url` that holds rows for
+/// `seasons`, and only `columns` of it (plus `season`) when given.
+///
+/// Over HTTP only the file footer and the column chunks of row groups whose
+/// `season` statistics overlap `seasons` are fetched, using Range requests
+/// with the same retry and mirror handling as [`download_dataframe`], and the
+/// result is cached under its own key. A fresh cached copy of the
+/// whole file is used instead when there is one. Servers that ignore Range
+/// headers or answer them with anything unusable, local sources, offline mode
+/// and `Config::range_requests = false` fall back to [`download_dataframe`].
+///
+/// Row groups are kept or skipped as a whole, so the result may include rows
+/// of other seasons and callers still filter it. Whole-file downloads keep
+/// every column, so callers passing `columns` still select them.
+pub fn download_parquet_seasons(
+    url: &str,
+    seasons: &[i32],
+    columns: Option<&[&str]>,
+) -> Result<DataFrame> {
+    let download = SeasonsDownload::new(url, seasons, columns);
+    if download.use_ranges() {
+        if let Some(df) = download.cached() {
+            return Ok(df);
+        }
+        if download.config.verbose {
+            eprintln!("Downloading row groups: {url}");
+        }
+        let read = read_row_groups(url, seasons, columns, &download.config);
+        if let Some(result) = download.store(read) {
+            return result;
+        }
     }
+    download.whole_file(download_dataframe(url, DataFormat::Parquet))
+}
 
-    let bytes = response.bytes()?;
-    let cursor = Cursor::new(bytes.as_ref());
+/// Async version of [`download_parquet_seasons`].
+pub async fn download_parquet_seasons_async(
+    url: &str,
+    seasons: &[i32],
+    columns: Option<&[&str]>,
+) -> Result<DataFrame> {
+    let download = Arc::new(SeasonsDownload::new(url, seasons, columns));
+    if download.use_ranges() {
+        let cached = Arc::clone(&download);
+        if let Some(df) = blocking(move || cached.cached()).await {
+            return Ok(df);
+        }
+        if download.config.verbose {
+            eprintln!("Downloading row groups: {url}");
+        }
+        let read = read_row_groups_async(url, seasons, columns, &download.config).await;
+        let store = Arc::clone(&download);
+        if let Some(result) = blocking(move || store.store(read)).await {
+            return result;
+        }
+    }
+    let df = download_dataframe_async(url, DataFormat::Parquet).await;
+    blocking(move || download.whole_file(df)).await
+}
+
+/// The cache side of a [`download_parquet_seasons`] call, shared by the sync
+/// and async versions.
+struct SeasonsDownload {
+    url: String,
+    cache_key: String,
+    /// Key of the cached selection of seasons.
+    partial_key: String,
+    config: Config,
+}
+
+impl SeasonsDownload {
+    fn new(url: &str, seasons: &[i32], columns: Option<&[&str]>) -> Self {
+        let config = get_config();
+        let cache_key = make_cache_key_in(url, &config);
+        SeasonsDownload {
+            url: url.to_string(),
+            partial_key: partial_cache_key(&cache_key, seasons, columns),
+            cache_key,
+            config,
+        }
+    }
+
+    /// Whether to try range requests rather than downloading the whole file.
+    fn use_ranges(&self) -> bool {
+        !self.config.offline && self.config.range_requests && local_path(&self.url).is_none()
+    }
+
+    /// A fresh cached copy of the whole file or of the selection.
+    fn cached(&self) -> Option<DataFrame> {
+        cache_get(&self.cache_key).or_else(|| cache_get(&self.partial_key))
+    }
+
+    /// Cache the result of a ranged read, or return None to download the
+    /// whole file instead after it failed.
+    fn store(&self, read: Result<RangedRead>) -> Option<Result<DataFrame>> {
+        match read {
+            Ok(RangedRead::Partial(df)) => {
+                cache_set(&self.partial_key, &df, &partial_metadata(&self.url));
+                Some(Ok(df))
+            }
+            Ok(RangedRead::Full(bytes, metadata)) => Some(store_body(
+                &self.cache_key,
+                &bytes,
+                DataFormat::Parquet,
+                &metadata,
+                &self.config,
+            )),
+            Err(e) => {
+                if self.config.verbose {
+                    eprintln!(
+                        "Range requests failed for {} ({e}); downloading the whole file",
+                        self.url
+                    );
+                }
+                None
+            }
+        }
+    }
+
+    /// The whole-file download, falling back in offline mode to a cached
+    /// selection of the seasons when the whole file was never cached.
+    fn whole_file(&self, df: Result<DataFrame>) -> Result<DataFrame> {
+        df.or_else(|error| match cache_get_stale(&self.partial_key) {
+            Some((df, _age)) if self.config.offline => Ok(df),
+            _ => Err(error),
+        })
+    }
+}
+
+/// The cache key for the `seasons` and `columns` of the file cached as
+/// `cache_key`.
+fn partial_cache_key(cache_key: &str, seasons: &[i32], columns: Option<&[&str]>) -> String {
+    let mut seasons = seasons.to_vec();
+    seasons.sort_unstable();
+    seasons.dedup();
+    let seasons: Vec<String> = seasons.iter().map(i32::to_string).collect();
+    let key = format!("{cache_key}.seasons-{}", seasons.join("-"));
+    match columns {
+        Some(columns) => columns_cache_key(&key, columns),
+        None => key,
+    }
+}
+
+/// The cache key for the `columns` of the entry cached as `cache_key`.
+/// Column names are hashed, as they can be many and long.
+fn columns_cache_key(cache_key: &str, columns: &[&str]) -> String {
+    let mut columns = columns.to_vec();
+    columns.sort_unstable();
+    columns.dedup();
+    format!("{cache_key}.columns-{:x}", md5::compute(columns.join(",")))
+}
+
+fn partial_metadata(url: &str) -> CacheMetadata {
+    CacheMetadata {
+        url: url.to_string(),
+        ..CacheMetadata::default()
+    }
+}
+
+/// Download the raw bytes at `url`, with the same retry, mirror and local
+/// source handling as [`download_dataframe`] but bypassing the cache.
+pub fn download_bytes(url: &str) -> Result<Bytes> {
+    let config = get_config();
+    if config.offline && local_path(url).is_none() {
+        return Err(NflReadError::NotCached(url.to_string()));
+    }
+    if config.verbose {
+        eprintln!("Downloading: {url}");
+    }
+    match fetch_with_mirrors(url, &config, None)? {
+        Fetched::Body(bytes, _) => Ok(bytes),
+        // Only a misbehaving server answers an unconditional request with 304.
+        Fetched::NotModified(_) => Err(NflReadError::NoData),
+    }
+}
+
+pub(crate) fn parse_dataframe(bytes: &[u8], format: DataFormat) -> Result<DataFrame> {
+    let cursor = Cursor::new(bytes);
 
     let df = match format {
         DataFormat::Parquet => ParquetReader::new(cursor).finish()?,
         DataFormat::Csv => {
-            let parse_options = CsvParseOptions::default()
-                .with_null_values(Some(NullValues::AllColumns(vec![
+            let parse_options =
+                CsvParseOptions::default().with_null_values(Some(NullValues::AllColumns(vec![
                     PlSmallStr::from("NA"),
                     PlSmallStr::from("NULL"),
                     PlSmallStr::from(""),
@@ -100,7 +1249,6 @@ pub fn download_dataframe(url: &str, format: DataFormat) -> Result<DataFrame> {
         }
     };
 
-    cache_set(&cache_key, &df);
     Ok(df)
 }
 
@@ -108,3 +1256,97 @@ pub fn download_nflverse(path: &str) -> Result<DataFrame> {
     let url = build_url(Repository::NflverseData, path, DataFormat::Parquet);
     download_dataframe(&url, DataFormat::Parquet)
 }
+
+/// Async version of [`download_nflverse`].
+pub async fn download_nflverse_async(path: &str) -> Result<DataFrame> {
+    let url = build_url(Repository::NflverseData, path, DataFormat::Parquet);
+    download_dataframe_async(&url, DataFormat::Parquet).await
+}
+
+/// Download an nflverse-data release path, fetching only the row groups for
+/// `seasons`, and of those only `columns`, when given. See
+/// [`download_parquet_seasons`].
+pub fn download_nflverse_seasons(
+    path: &str,
+    seasons: Option<&[i32]>,
+    columns: Option<&[&str]>,
+) -> Result<DataFrame> {
+    let url = build_url(Repository::NflverseData, path, DataFormat::Parquet);
+    match seasons {
+        Some(seasons) => download_parquet_seasons(&url, seasons, columns),
+        None => download_dataframe(&url, DataFormat::Parquet),
+    }
+}
+
+/// Async version of [`download_nflverse_seasons`].
+pub async fn download_nflverse_seasons_async(
+    path: &str,
+    seasons: Option<&[i32]>,
+    columns: Option<&[&str]>,
+) -> Result<DataFrame> {
+    let url = build_url(Repository::NflverseData, path, DataFormat::Parquet);
+    match seasons {
+        Some(seasons) => download_parquet_seasons_async(&url, seasons, columns).await,
+        None => download_dataframe_async(&url, DataFormat::Parquet).await,
+    }
+}
+
+/// Download several nflverse-data release paths, preserving their order.
+pub fn download_nflverse_all(paths: &[String]) -> Result<Vec<DataFrame>> {
+    download_all(&nflverse_urls(paths), DataFormat::Parquet)
+}
+
+/// Async version of [`download_nflverse_all`].
+pub async fn download_nflverse_all_async(paths: &[String]) -> Result<Vec<DataFrame>> {
+    download_all_async(&nflverse_urls(paths), DataFormat::Parquet).await
+}
+
+/// Download several nflverse-data release paths with
+/// [`download_all_columns`], reading only `columns` from each.
+pub fn download_nflverse_all_columns(paths: &[String], columns: &[&str]) -> Result<Vec<DataFrame>> {
+    download_all_columns(&nflverse_urls(paths), columns)
+}
+
+/// Async version of [`download_nflverse_all_columns`].
+pub async fn download_nflverse_all_columns_async(
+    paths: &[String],
+    columns: &[&str],
+) -> Result<Vec<DataFrame>> {
+    download_all_columns_async(&nflverse_urls(paths), columns).await
+}
+
+/// Scan a single nflverse-data release path with [`scan_parquet`].
+pub fn scan_nflverse(path: &str) -> Result<LazyFrame> {
+    scan_parquet(&build_url(
+        Repository::NflverseData,
+        path,
+        DataFormat::Parquet,
+    ))
+}
+
+/// Async version of [`scan_nflverse`].
+pub async fn scan_nflverse_async(path: &str) -> Result<LazyFrame> {
+    scan_parquet_async(&build_url(
+        Repository::NflverseData,
+        path,
+        DataFormat::Parquet,
+    ))
+    .await
+}
+
+/// Scan several nflverse-data release paths, preserving their order.
+pub fn scan_nflverse_all(paths: &[String]) -> Result<Vec<LazyFrame>> {
+    scan_all(&nflverse_urls(paths))
+}
+
+/// Async version of [`scan_nflverse_all`].
+pub async fn scan_nflverse_all_async(paths: &[String]) -> Result<Vec<LazyFrame>> {
+    scan_all_async(&nflverse_urls(paths)).await
+}
+
+fn nflverse_urls(paths: &[String]) -> Vec<String> {
+    paths
+        .iter()
+        .map(|path| build_url(Repository::NflverseData, path, DataFormat::Parquet))
+        .collect()
+}
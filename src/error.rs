@@ -19,6 +19,9 @@ pub enum NflReadError {
 
     #[error("No data available for the requested parameters")]
     NoData,
+
+    #[error("Offline mode: {0} is not in the cache")]
+    NotCached(String),
 }
 
 pub type Result<T> = std::result::Result<T, NflReadError>;
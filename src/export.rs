@@ -0,0 +1,170 @@
+//! Writing loaded DataFrames to CSV, Parquet, JSON, NDJSON and Arrow IPC.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use polars::prelude::*;
+
+use crate::error::{NflReadError, Result};
+
+/// An output file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Parquet,
+    /// A JSON array of row objects.
+    Json,
+    /// One JSON object per line.
+    Ndjson,
+    /// Arrow IPC file (Feather v2).
+    Ipc,
+}
+
+impl ExportFormat {
+    /// Infer the format from a file extension: `csv`, `parquet`/`pq`,
+    /// `json`, `ndjson`/`jsonl`, or `arrow`/`ipc`/`feather`.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        ext.parse().ok()
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = NflReadError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "parquet" | "pq" => Ok(ExportFormat::Parquet),
+            "json" => Ok(ExportFormat::Json),
+            "ndjson" | "jsonl" => Ok(ExportFormat::Ndjson),
+            "ipc" | "arrow" | "feather" => Ok(ExportFormat::Ipc),
+            _ => Err(NflReadError::InvalidParameter(format!(
+                "Invalid format: '{s}'. Must be one of: csv, parquet, json, ndjson, ipc"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Parquet => "parquet",
+            ExportFormat::Json => "json",
+            ExportFormat::Ndjson => "ndjson",
+            ExportFormat::Ipc => "ipc",
+        })
+    }
+}
+
+/// Compression codec for Parquet output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Compression {
+    Uncompressed,
+    Snappy,
+    Gzip,
+    Lz4,
+    #[default]
+    Zstd,
+    Brotli,
+}
+
+impl From<Compression> for ParquetCompression {
+    fn from(compression: Compression) -> Self {
+        match compression {
+            Compression::Uncompressed => ParquetCompression::Uncompressed,
+            Compression::Snappy => ParquetCompression::Snappy,
+            Compression::Gzip => ParquetCompression::Gzip(None),
+            Compression::Lz4 => ParquetCompression::Lz4Raw,
+            Compression::Zstd => ParquetCompression::Zstd(None),
+            Compression::Brotli => ParquetCompression::Brotli(None),
+        }
+    }
+}
+
+impl FromStr for Compression {
+    type Err = NflReadError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "uncompressed" | "none" => Ok(Compression::Uncompressed),
+            "snappy" => Ok(Compression::Snappy),
+            "gzip" => Ok(Compression::Gzip),
+            "lz4" => Ok(Compression::Lz4),
+            "zstd" => Ok(Compression::Zstd),
+            "brotli" => Ok(Compression::Brotli),
+            _ => Err(NflReadError::InvalidParameter(format!(
+                "Invalid compression: '{s}'. \
+                 Must be one of: uncompressed, snappy, gzip, lz4, zstd, brotli"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Compression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Compression::Uncompressed => "uncompressed",
+            Compression::Snappy => "snappy",
+            Compression::Gzip => "gzip",
+            Compression::Lz4 => "lz4",
+            Compression::Zstd => "zstd",
+            Compression::Brotli => "brotli",
+        })
+    }
+}
+
+/// Write `df` to `writer` in the given format. `compression` only applies
+/// to Parquet.
+pub fn write_dataframe<W: Write>(
+    df: &mut DataFrame,
+    mut writer: W,
+    format: ExportFormat,
+    compression: Compression,
+) -> Result<()> {
+    match format {
+        ExportFormat::Csv => CsvWriter::new(&mut writer).finish(df)?,
+        ExportFormat::Parquet => {
+            ParquetWriter::new(&mut writer)
+                .with_compression(compression.into())
+                .finish(df)?;
+        }
+        ExportFormat::Ipc => IpcWriter::new(&mut writer).finish(df)?,
+        ExportFormat::Json => {
+            JsonWriter::new(&mut writer)
+                .with_json_format(JsonFormat::Json)
+                .finish(df)?;
+            writeln!(writer)?;
+        }
+        ExportFormat::Ndjson => {
+            JsonWriter::new(&mut writer)
+                .with_json_format(JsonFormat::JsonLines)
+                .finish(df)?;
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Write `df` to the file at `path`. When `format` is `None` it is inferred
+/// from the file extension.
+pub fn export_dataframe(
+    df: &mut DataFrame,
+    path: &Path,
+    format: Option<ExportFormat>,
+    compression: Compression,
+) -> Result<()> {
+    let Some(format) = format.or_else(|| ExportFormat::from_path(path)) else {
+        return Err(NflReadError::InvalidParameter(format!(
+            "Cannot infer the output format of {}; \
+             use a .csv, .parquet, .json, .ndjson or .arrow extension",
+            path.display()
+        )));
+    };
+    let file = BufWriter::new(File::create(path)?);
+    write_dataframe(df, file, format, compression)
+}
@@ -1,35 +1,91 @@
 pub mod cache;
+pub mod catalog;
+pub mod client;
 pub mod config;
 pub mod downloader;
 pub mod error;
+pub mod export;
 pub mod loaders;
+pub mod params;
+mod ranged;
+pub mod snapshot;
 pub mod utils_date;
 
-pub use config::{CacheMode, Config};
+pub use catalog::{Dataset, datasets, find_dataset, lookup_dataset};
+pub use config::{CacheMode, Config, RetryPolicy};
+pub use downloader::{Freshness, track_freshness, track_freshness_async};
 pub use error::NflReadError;
-pub use utils_date::{get_current_season, get_current_week};
+pub use export::{Compression, ExportFormat, export_dataframe, write_dataframe};
+pub use loaders::seasons::Seasons;
+pub use params::{
+    ModelVersion, NgsStatType, OpportunityStatType, PfrStatType, PfrSummaryLevel, RankingType,
+    SummaryLevel,
+};
+pub use utils_date::{get_current_season, get_current_week, get_current_week_async};
 
-// Re-export all loader functions at the crate root
-pub use loaders::load_combine::load_combine;
-pub use loaders::load_contracts::load_contracts;
-pub use loaders::load_depth_charts::load_depth_charts;
-pub use loaders::load_draft_picks::load_draft_picks;
-pub use loaders::load_ffverse::{load_ff_opportunity, load_ff_playerids, load_ff_rankings};
-pub use loaders::load_ftn_charting::load_ftn_charting;
-pub use loaders::load_injuries::load_injuries;
-pub use loaders::load_nextgen_stats::load_nextgen_stats;
-pub use loaders::load_officials::load_officials;
-pub use loaders::load_participation::load_participation;
-pub use loaders::load_pbp::load_pbp;
-pub use loaders::load_pfr_advstats::load_pfr_advstats;
-pub use loaders::load_player_stats::load_player_stats;
-pub use loaders::load_players::load_players;
-pub use loaders::load_rosters::load_rosters;
-pub use loaders::load_rosters_weekly::load_rosters_weekly;
-pub use loaders::load_schedules::load_schedules;
-pub use loaders::load_snap_counts::load_snap_counts;
-pub use loaders::load_team_stats::load_team_stats;
-pub use loaders::load_teams::load_teams;
-pub use loaders::load_trades::load_trades;
+// Re-export all loader functions (and their async and lazy twins) at the crate root
+pub use loaders::load_combine::{
+    load_combine, load_combine_async, scan_combine, scan_combine_async,
+};
+pub use loaders::load_contracts::{
+    load_contracts, load_contracts_async, scan_contracts, scan_contracts_async,
+};
+pub use loaders::load_dataset::{
+    LoadParams, load, load_async, load_from_params, load_from_params_async, load_with_info,
+    load_with_info_async,
+};
+pub use loaders::load_depth_charts::{
+    load_depth_charts, load_depth_charts_async, scan_depth_charts, scan_depth_charts_async,
+};
+pub use loaders::load_draft_picks::{
+    load_draft_picks, load_draft_picks_async, scan_draft_picks, scan_draft_picks_async,
+};
+pub use loaders::load_ffverse::{
+    load_ff_opportunity, load_ff_opportunity_async, load_ff_playerids, load_ff_playerids_async,
+    load_ff_rankings, load_ff_rankings_async,
+};
+pub use loaders::load_ftn_charting::{
+    load_ftn_charting, load_ftn_charting_async, scan_ftn_charting, scan_ftn_charting_async,
+};
+pub use loaders::load_injuries::{
+    load_injuries, load_injuries_async, scan_injuries, scan_injuries_async,
+};
+pub use loaders::load_nextgen_stats::{
+    load_nextgen_stats, load_nextgen_stats_async, scan_nextgen_stats, scan_nextgen_stats_async,
+};
+pub use loaders::load_officials::{
+    load_officials, load_officials_async, scan_officials, scan_officials_async,
+};
+pub use loaders::load_participation::{
+    load_participation, load_participation_async, scan_participation, scan_participation_async,
+};
+pub use loaders::load_pbp::{load_pbp, load_pbp_async, scan_pbp, scan_pbp_async};
+pub use loaders::load_pfr_advstats::{
+    load_pfr_advstats, load_pfr_advstats_async, scan_pfr_advstats, scan_pfr_advstats_async,
+};
+pub use loaders::load_player_stats::{
+    load_player_stats, load_player_stats_async, scan_player_stats, scan_player_stats_async,
+};
+pub use loaders::load_players::{
+    load_players, load_players_async, scan_players, scan_players_async,
+};
+pub use loaders::load_rosters::{
+    load_rosters, load_rosters_async, scan_rosters, scan_rosters_async,
+};
+pub use loaders::load_rosters_weekly::{
+    load_rosters_weekly, load_rosters_weekly_async, scan_rosters_weekly, scan_rosters_weekly_async,
+};
+pub use loaders::load_schedules::{
+    load_schedules, load_schedules_async, scan_schedules, scan_schedules_async,
+};
+pub use loaders::load_snap_counts::{
+    load_snap_counts, load_snap_counts_async, scan_snap_counts, scan_snap_counts_async,
+};
+pub use loaders::load_team_stats::{
+    load_team_stats, load_team_stats_async, scan_team_stats, scan_team_stats_async,
+};
+pub use loaders::load_teams::{load_teams, load_teams_async, scan_teams, scan_teams_async};
+pub use loaders::load_trades::{load_trades, load_trades_async, scan_trades, scan_trades_async};
 
-pub use cache::clear_cache;
+pub use cache::{cache_entries, clear_cache, clear_dataset_cache, prune_cache};
+pub use snapshot::snapshot;
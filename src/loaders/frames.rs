@@ -0,0 +1,228 @@
+use polars::prelude::*;
+
+use crate::downloader::{
+    blocking, download_nflverse_all, download_nflverse_all_async, download_nflverse_all_columns,
+    download_nflverse_all_columns_async, download_nflverse_seasons,
+    download_nflverse_seasons_async,
+};
+use crate::error::{NflReadError, Result};
+
+/// Concatenate per-season DataFrames diagonally, keeping season order.
+pub fn concat_frames(dfs: Vec<DataFrame>) -> Result<DataFrame> {
+    if dfs.len() == 1 {
+        return Ok(dfs.into_iter().next().unwrap());
+    }
+
+    let lazy_frames: Vec<LazyFrame> = dfs.into_iter().map(|df| df.lazy()).collect();
+    Ok(concat_lazy(lazy_frames)?.collect()?)
+}
+
+/// Async version of [`concat_frames`], run on the blocking pool so a
+/// multi-season concatenation does not stall the runtime.
+pub async fn concat_frames_async(dfs: Vec<DataFrame>) -> Result<DataFrame> {
+    blocking(move || concat_frames(dfs)).await
+}
+
+/// Concatenate per-season LazyFrames diagonally, keeping season order.
+pub fn concat_lazy(lfs: Vec<LazyFrame>) -> Result<LazyFrame> {
+    if lfs.len() == 1 {
+        return Ok(lfs.into_iter().next().unwrap());
+    }
+
+    Ok(concat(
+        lfs,
+        UnionArgs {
+            parallel: true,
+            rechunk: true,
+            to_supertypes: true,
+            diagonal: true,
+            from_partitioned_ds: false,
+            maintain_order: true,
+        },
+    )?)
+}
+
+/// A per-season frame cut down to the requested columns, with the names of
+/// any requested columns that season does not have.
+pub struct Selected {
+    df: DataFrame,
+    missing: Vec<String>,
+}
+
+/// Keep only `columns` of a per-season frame, in the given order, so seasons
+/// can be projected before they are concatenated. Columns a season lacks are
+/// filled with nulls, as a diagonal concatenation would. `None` keeps every
+/// column.
+pub fn select_columns(df: DataFrame, columns: Option<&[&str]>) -> Result<Selected> {
+    let Some(columns) = columns else {
+        return Ok(Selected {
+            df,
+            missing: Vec::new(),
+        });
+    };
+    if columns.is_empty() {
+        return Err(NflReadError::InvalidParameter(
+            "At least one column must be selected".to_string(),
+        ));
+    }
+    let mut missing = Vec::new();
+    let mut selected = Vec::with_capacity(columns.len());
+    for &name in columns {
+        match df.column(name) {
+            Ok(column) => selected.push(column.clone()),
+            Err(_) => {
+                missing.push(name.to_string());
+                selected.push(Column::full_null(name.into(), df.height(), &DataType::Null));
+            }
+        }
+    }
+    Ok(Selected {
+        df: DataFrame::new(selected)?,
+        missing,
+    })
+}
+
+/// Download the per-season release `paths` of `dataset` and concatenate
+/// them, reading only `columns` from each file when given.
+///
+/// The names are checked against the most recent season, which is read
+/// first, so an unknown column fails before the other seasons are
+/// downloaded. Older seasons lacking a column get nulls, as in
+/// [`select_columns`].
+pub fn download_seasons(
+    dataset: &str,
+    paths: &[String],
+    columns: Option<&[&str]>,
+) -> Result<DataFrame> {
+    let Some(columns) = columns else {
+        return concat_frames(download_nflverse_all(paths)?);
+    };
+    let (latest, older) = split_latest(paths, columns)?;
+    let latest = download_nflverse_all_columns(latest, columns)?;
+    let latest = known_columns(dataset, latest, columns)?;
+    let frames = download_nflverse_all_columns(older, columns)?;
+    concat_frames(with_latest(frames, latest, columns)?)
+}
+
+/// Async version of [`download_seasons`].
+pub async fn download_seasons_async(
+    dataset: &str,
+    paths: &[String],
+    columns: Option<&[&str]>,
+) -> Result<DataFrame> {
+    let Some(columns) = columns else {
+        return concat_frames_async(download_nflverse_all_async(paths).await?).await;
+    };
+    let (latest, older) = split_latest(paths, columns)?;
+    let latest = download_nflverse_all_columns_async(latest, columns).await?;
+    let latest = known_columns(dataset, latest, columns)?;
+    let frames = download_nflverse_all_columns_async(older, columns).await?;
+    concat_frames_async(with_latest(frames, latest, columns)?).await
+}
+
+/// Download the single-file release `path` and filter it to `seasons`.
+///
+/// Only the row groups that may hold `seasons`, and of those only `columns`
+/// (plus `season`), are fetched where Range requests allow. Whole-file
+/// downloads keep every column, so callers passing `columns` still select
+/// them.
+pub fn download_season_file(
+    path: &str,
+    seasons: Option<Vec<i32>>,
+    columns: Option<&[&str]>,
+) -> Result<DataFrame> {
+    let df = download_nflverse_seasons(path, seasons.as_deref(), columns)?;
+    filter_seasons(df, seasons)
+}
+
+/// Async version of [`download_season_file`].
+pub async fn download_season_file_async(
+    path: &str,
+    seasons: Option<Vec<i32>>,
+    columns: Option<&[&str]>,
+) -> Result<DataFrame> {
+    let df = download_nflverse_seasons_async(path, seasons.as_deref(), columns).await?;
+    filter_seasons_async(df, seasons).await
+}
+
+/// The most recent season's path, as a one-element slice, and the others.
+fn split_latest<'a>(paths: &'a [String], columns: &[&str]) -> Result<(&'a [String], &'a [String])> {
+    if columns.is_empty() {
+        return Err(NflReadError::InvalidParameter(
+            "At least one column must be selected".to_string(),
+        ));
+    }
+    let split = paths.len().saturating_sub(1);
+    Ok((&paths[split..], &paths[..split]))
+}
+
+/// The most recent season's frame, failing with `InvalidParameter` if it
+/// lacks any of `columns`.
+fn known_columns(dataset: &str, latest: Vec<DataFrame>, columns: &[&str]) -> Result<DataFrame> {
+    let Some(df) = latest.into_iter().next() else {
+        return Ok(DataFrame::empty());
+    };
+    let selected = select_columns(df, Some(columns))?;
+    if let Some(name) = selected.missing.first() {
+        return Err(NflReadError::InvalidParameter(format!(
+            "Unknown column for {dataset}: '{name}'"
+        )));
+    }
+    Ok(selected.df)
+}
+
+/// The older seasons' frames, with the missing columns filled in, followed
+/// by the most recent season's.
+fn with_latest(
+    older: Vec<DataFrame>,
+    latest: DataFrame,
+    columns: &[&str],
+) -> Result<Vec<DataFrame>> {
+    let mut frames = Vec::with_capacity(older.len() + 1);
+    for df in older {
+        frames.push(select_columns(df, Some(columns))?.df);
+    }
+    frames.push(latest);
+    Ok(frames)
+}
+
+/// Concatenate projected seasons with [`concat_frames`], failing with
+/// `InvalidParameter` if a requested column is in none of them.
+pub fn concat_selected(dataset: &str, frames: Vec<Selected>) -> Result<DataFrame> {
+    if let Some(first) = frames.first() {
+        for name in &first.missing {
+            if frames.iter().all(|f| f.missing.contains(name)) {
+                return Err(NflReadError::InvalidParameter(format!(
+                    "Unknown column for {dataset}: '{name}'"
+                )));
+            }
+        }
+    }
+    concat_frames(frames.into_iter().map(|f| f.df).collect())
+}
+
+/// Filter a single-file dataset down to the requested seasons.
+/// `None` keeps every season.
+pub fn filter_seasons(df: DataFrame, seasons: Option<Vec<i32>>) -> Result<DataFrame> {
+    match seasons {
+        None => Ok(df),
+        Some(_) => Ok(filter_seasons_lazy(df.lazy(), seasons).collect()?),
+    }
+}
+
+/// Lazy version of [`filter_seasons`], for the `scan_*` variants of
+/// single-file datasets.
+pub fn filter_seasons_lazy(lf: LazyFrame, seasons: Option<Vec<i32>>) -> LazyFrame {
+    match seasons {
+        None => lf,
+        Some(season_list) => {
+            let season_series = Series::new(PlSmallStr::from("seasons"), &season_list);
+            lf.filter(col("season").is_in(lit(season_series)))
+        }
+    }
+}
+
+/// Async version of [`filter_seasons`], run on the blocking pool.
+pub async fn filter_seasons_async(df: DataFrame, seasons: Option<Vec<i32>>) -> Result<DataFrame> {
+    blocking(move || filter_seasons(df, seasons)).await
+}
@@ -1,9 +1,27 @@
-use polars::prelude::DataFrame;
+use polars::prelude::{DataFrame, LazyFrame};
 
-use crate::downloader::download_nflverse;
+use crate::catalog::CONTRACTS;
+use crate::downloader::{
+    download_nflverse, download_nflverse_async, scan_nflverse, scan_nflverse_async,
+};
 use crate::error::Result;
 
 /// Load historical contract data. No season parameter needed.
 pub fn load_contracts() -> Result<DataFrame> {
-    download_nflverse("contracts/historical_contracts")
+    download_nflverse(&CONTRACTS.path(&[])?)
+}
+
+/// Async version of [`load_contracts`].
+pub async fn load_contracts_async() -> Result<DataFrame> {
+    download_nflverse_async(&CONTRACTS.path(&[])?).await
+}
+
+/// Lazy version of [`load_contracts`]. See [`crate::downloader::scan_parquet`].
+pub fn scan_contracts() -> Result<LazyFrame> {
+    scan_nflverse(&CONTRACTS.path(&[])?)
+}
+
+/// Async version of [`scan_contracts`].
+pub async fn scan_contracts_async() -> Result<LazyFrame> {
+    scan_nflverse_async(&CONTRACTS.path(&[])?).await
 }
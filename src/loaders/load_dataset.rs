@@ -0,0 +1,391 @@
+use std::collections::BTreeMap;
+
+use polars::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::catalog::{self, Dataset, lookup_dataset};
+use crate::downloader::{Freshness, blocking, track_freshness, track_freshness_async};
+use crate::error::{NflReadError, Result};
+use crate::loaders::frames::{concat_selected, select_columns};
+use crate::loaders::load_combine::{load_combine_columns, load_combine_columns_async};
+use crate::loaders::load_contracts::{load_contracts, load_contracts_async};
+use crate::loaders::load_depth_charts::{load_depth_charts, load_depth_charts_async};
+use crate::loaders::load_draft_picks::{load_draft_picks_columns, load_draft_picks_columns_async};
+use crate::loaders::load_ffverse::{
+    load_ff_opportunity, load_ff_opportunity_async, load_ff_playerids, load_ff_playerids_async,
+    load_ff_rankings, load_ff_rankings_async,
+};
+use crate::loaders::load_ftn_charting::{load_ftn_charting, load_ftn_charting_async};
+use crate::loaders::load_injuries::{load_injuries, load_injuries_async};
+use crate::loaders::load_nextgen_stats::{
+    load_nextgen_stats_columns, load_nextgen_stats_columns_async,
+};
+use crate::loaders::load_officials::{load_officials_columns, load_officials_columns_async};
+use crate::loaders::load_participation::{load_participation, load_participation_async};
+use crate::loaders::load_pbp::{load_pbp, load_pbp_async};
+use crate::loaders::load_pfr_advstats::{
+    load_pfr_advstats_columns, load_pfr_advstats_columns_async,
+};
+use crate::loaders::load_player_stats::{load_player_stats, load_player_stats_async};
+use crate::loaders::load_players::{load_players, load_players_async};
+use crate::loaders::load_rosters::{load_rosters, load_rosters_async};
+use crate::loaders::load_rosters_weekly::{load_rosters_weekly, load_rosters_weekly_async};
+use crate::loaders::load_schedules::{load_schedules_columns, load_schedules_columns_async};
+use crate::loaders::load_snap_counts::{load_snap_counts, load_snap_counts_async};
+use crate::loaders::load_team_stats::{load_team_stats, load_team_stats_async};
+use crate::loaders::load_teams::{load_teams, load_teams_async};
+use crate::loaders::load_trades::{load_trades, load_trades_async};
+use crate::loaders::seasons::Seasons;
+
+/// Arguments for [`load`]: the seasons plus any dataset parameters by name.
+///
+/// Deserializes from a flat table, e.g. in TOML:
+///
+/// ```toml
+/// dataset = "player_stats"
+/// seasons = [2023]
+/// summary_level = "week"
+/// ```
+///
+/// Parameter values may be strings, numbers or booleans.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LoadParams {
+    /// Dataset to load with [`load_from_params`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dataset: Option<String>,
+    /// Seasons to load; `None` loads the current season. In TOML, either a
+    /// year, a list of years or a string such as `"2015.."` or `"all"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seasons: Option<Seasons>,
+    /// Columns to keep, in order; `None` keeps every column. Per-season
+    /// datasets whose loader takes a `columns` argument (`pbp`,
+    /// `participation`, `player_stats` and `ftn_charting`, and `pfr_advstats`
+    /// by week) project each season as it is read, and single-file datasets
+    /// read with Range requests fetch only those columns.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub columns: Option<Vec<String>>,
+    /// Dataset parameters such as `summary_level` or `stat_type`. Missing
+    /// parameters take their catalog default.
+    #[serde(flatten, deserialize_with = "deserialize_params")]
+    pub params: BTreeMap<String, String>,
+}
+
+impl LoadParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn dataset(mut self, dataset: &str) -> Self {
+        self.dataset = Some(dataset.to_string());
+        self
+    }
+
+    pub fn seasons(mut self, seasons: impl Into<Seasons>) -> Self {
+        self.seasons = Some(seasons.into());
+        self
+    }
+
+    pub fn columns<S: AsRef<str>>(mut self, columns: &[S]) -> Self {
+        self.columns = Some(columns.iter().map(|c| c.as_ref().to_string()).collect());
+        self
+    }
+
+    pub fn param(mut self, name: &str, value: &str) -> Self {
+        self.params.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    fn column_refs(&self) -> Option<Vec<&str>> {
+        self.columns
+            .as_ref()
+            .map(|columns| columns.iter().map(String::as_str).collect())
+    }
+}
+
+/// A parameter value as written in a configuration file.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Scalar {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+}
+
+fn deserialize_params<'de, D>(
+    deserializer: D,
+) -> std::result::Result<BTreeMap<String, String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let params = BTreeMap::<String, Scalar>::deserialize(deserializer)?;
+    Ok(params
+        .into_iter()
+        .map(|(name, value)| {
+            let value = match value {
+                Scalar::Bool(b) => b.to_string(),
+                Scalar::Int(i) => i.to_string(),
+                Scalar::Float(f) => f.to_string(),
+                Scalar::String(s) => s,
+            };
+            (name, value)
+        })
+        .collect())
+}
+
+/// Load a dataset by its catalog name (see [`crate::catalog`]).
+///
+/// Parameters are validated against the catalog before anything is
+/// downloaded; unknown datasets, parameters or values return
+/// `InvalidParameter` listing the valid options. A `dataset` in `params`
+/// must name the same dataset.
+pub fn load(dataset: &str, params: &LoadParams) -> Result<DataFrame> {
+    let (dataset, values) = resolve(dataset, params)?;
+    let columns = params.column_refs();
+    let seasons = params.seasons.clone().unwrap_or_default();
+    let df = load_resolved(dataset, seasons, columns.as_deref(), &values)?;
+    project(dataset, df, columns.as_deref())
+}
+
+/// Async version of [`load`].
+pub async fn load_async(dataset: &str, params: &LoadParams) -> Result<DataFrame> {
+    let (dataset, values) = resolve(dataset, params)?;
+    let columns = params.column_refs();
+    let seasons = params.seasons.clone().unwrap_or_default();
+    let df = load_resolved_async(dataset, seasons, columns.as_deref(), &values).await?;
+    let columns = params.columns.clone();
+    blocking(move || {
+        let columns: Option<Vec<&str>> = columns
+            .as_ref()
+            .map(|c| c.iter().map(String::as_str).collect());
+        project(dataset, df, columns.as_deref())
+    })
+    .await
+}
+
+/// Keep only `columns` of a loaded dataset. Loaders with a `columns`
+/// argument have already projected their seasons, which this leaves as is.
+fn project(dataset: &Dataset, df: DataFrame, columns: Option<&[&str]>) -> Result<DataFrame> {
+    if columns.is_none() {
+        return Ok(df);
+    }
+    concat_selected(dataset.name, vec![select_columns(df, columns)?])
+}
+
+/// Like [`load`], also reporting whether any of the data was served from an
+/// expired cache entry. See [`track_freshness`].
+pub fn load_with_info(dataset: &str, params: &LoadParams) -> Result<(DataFrame, Freshness)> {
+    let (df, freshness) = track_freshness(|| load(dataset, params));
+    Ok((df?, freshness))
+}
+
+/// Async version of [`load_with_info`].
+pub async fn load_with_info_async(
+    dataset: &str,
+    params: &LoadParams,
+) -> Result<(DataFrame, Freshness)> {
+    let (df, freshness) = track_freshness_async(load_async(dataset, params)).await;
+    Ok((df?, freshness))
+}
+
+/// Load the dataset named by the `dataset` key of `params`. See [`load`].
+pub fn load_from_params(params: &LoadParams) -> Result<DataFrame> {
+    load(params_dataset(params)?, params)
+}
+
+/// Async version of [`load_from_params`].
+pub async fn load_from_params_async(params: &LoadParams) -> Result<DataFrame> {
+    load_async(params_dataset(params)?, params).await
+}
+
+fn params_dataset(params: &LoadParams) -> Result<&str> {
+    params
+        .dataset
+        .as_deref()
+        .ok_or_else(|| NflReadError::InvalidParameter("No dataset given".to_string()))
+}
+
+/// Define `load_resolved` and `load_resolved_async`, calling each catalog
+/// dataset's loader with the seasons, the selected columns and its
+/// parameters bound by name.
+///
+/// The arms must list every catalog dataset, in catalog order, with its
+/// parameters in catalog order; anything else fails to compile.
+macro_rules! loaders {
+    (
+        |$seasons:ident, $columns:ident|
+        $(
+            $dataset:ident $([$($param:ident),*])? =>
+                $load:ident, $load_async:ident ($($arg:expr),*);
+        )*
+    ) => {
+        const _: () = assert!(
+            matches_catalog(&[$((catalog::$dataset.name, &[$($(stringify!($param)),*)?])),*]),
+            "load_dataset needs one loader per catalog dataset and parameter, in catalog order"
+        );
+
+        fn load_resolved(
+            dataset: &Dataset,
+            $seasons: Seasons,
+            $columns: Option<&[&str]>,
+            values: &[(&str, String)],
+        ) -> Result<DataFrame> {
+            $(if dataset.name == catalog::$dataset.name {
+                $($(let $param = param_value(values, stringify!($param));)*)?
+                return $load($($arg),*);
+            })*
+            Err(no_loader(dataset))
+        }
+
+        async fn load_resolved_async(
+            dataset: &Dataset,
+            $seasons: Seasons,
+            $columns: Option<&[&str]>,
+            values: &[(&str, String)],
+        ) -> Result<DataFrame> {
+            $(if dataset.name == catalog::$dataset.name {
+                $($(let $param = param_value(values, stringify!($param));)*)?
+                return $load_async($($arg),*).await;
+            })*
+            Err(no_loader(dataset))
+        }
+    };
+}
+
+loaders! {
+    |seasons, columns|
+    PBP => load_pbp, load_pbp_async(seasons, columns);
+    PARTICIPATION => load_participation, load_participation_async(seasons, columns);
+    SCHEDULES => load_schedules_columns, load_schedules_columns_async(seasons, columns);
+    PLAYER_STATS [summary_level] =>
+        load_player_stats, load_player_stats_async(seasons, summary_level.parse()?, columns);
+    TEAM_STATS [summary_level] =>
+        load_team_stats, load_team_stats_async(seasons, summary_level.parse()?);
+    NEXTGEN_STATS [stat_type] => load_nextgen_stats_columns, load_nextgen_stats_columns_async(
+        seasons,
+        stat_type.parse()?,
+        columns
+    );
+    PFR_ADVSTATS [stat_type, summary_level] =>
+        load_pfr_advstats_columns, load_pfr_advstats_columns_async(
+            seasons,
+            stat_type.parse()?,
+            summary_level.parse()?,
+            columns
+        );
+    SNAP_COUNTS => load_snap_counts, load_snap_counts_async(seasons);
+    ROSTERS => load_rosters, load_rosters_async(seasons);
+    ROSTERS_WEEKLY => load_rosters_weekly, load_rosters_weekly_async(seasons);
+    PLAYERS => load_players, load_players_async();
+    DEPTH_CHARTS => load_depth_charts, load_depth_charts_async(seasons);
+    INJURIES => load_injuries, load_injuries_async(seasons);
+    OFFICIALS => load_officials_columns, load_officials_columns_async(seasons, columns);
+    DRAFT_PICKS => load_draft_picks_columns, load_draft_picks_columns_async(seasons, columns);
+    COMBINE => load_combine_columns, load_combine_columns_async(seasons, columns);
+    CONTRACTS => load_contracts, load_contracts_async();
+    TRADES => load_trades, load_trades_async();
+    TEAMS => load_teams, load_teams_async();
+    FTN_CHARTING => load_ftn_charting, load_ftn_charting_async(seasons, columns);
+    FF_PLAYERIDS => load_ff_playerids, load_ff_playerids_async();
+    FF_RANKINGS [ranking_type] =>
+        load_ff_rankings, load_ff_rankings_async(ranking_type.parse()?);
+    FF_OPPORTUNITY [stat_type, model_version] => load_ff_opportunity, load_ff_opportunity_async(
+        seasons,
+        stat_type.parse()?,
+        model_version.parse()?
+    );
+}
+
+/// Whether `loaders` names every catalog dataset and its parameters, in order.
+const fn matches_catalog(loaders: &[(&str, &[&str])]) -> bool {
+    let catalog = catalog::DATASETS;
+    if loaders.len() != catalog.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < loaders.len() {
+        let (name, params) = loaders[i];
+        let dataset = &catalog[i];
+        if !str_eq(name, dataset.name) || params.len() != dataset.parameters.len() {
+            return false;
+        }
+        let mut j = 0;
+        while j < params.len() {
+            if !str_eq(params[j], dataset.parameters[j].name) {
+                return false;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// The resolved value of parameter `name`.
+fn param_value<'a>(values: &'a [(&str, String)], name: &str) -> &'a str {
+    values
+        .iter()
+        .find(|(param, _)| *param == name)
+        .map_or("", |(_, value)| value)
+}
+
+fn no_loader(dataset: &Dataset) -> NflReadError {
+    NflReadError::InvalidParameter(format!("No loader for dataset '{}'", dataset.name))
+}
+
+/// Look up and validate `dataset`, returning its parameter values in catalog
+/// order with defaults filled in.
+fn resolve(
+    dataset: &str,
+    params: &LoadParams,
+) -> Result<(&'static Dataset, Vec<(&'static str, String)>)> {
+    let dataset = lookup_dataset(dataset)?;
+    if let Some(named) = params
+        .dataset
+        .as_deref()
+        .filter(|named| *named != dataset.name)
+    {
+        return Err(NflReadError::InvalidParameter(format!(
+            "Parameters are for dataset '{named}', not {}",
+            dataset.name
+        )));
+    }
+
+    for (name, value) in &params.params {
+        dataset.validate_param(name, value)?;
+    }
+    if let Some(seasons) = &params.seasons {
+        if !dataset.accepts_seasons {
+            return Err(NflReadError::InvalidParameter(format!(
+                "{} does not take seasons",
+                dataset.name
+            )));
+        }
+        dataset.season_filter(seasons)?;
+    }
+
+    let values = dataset
+        .parameters
+        .iter()
+        .map(|p| {
+            let value = params.params.get(p.name).cloned();
+            (p.name, value.unwrap_or(p.default.to_string()))
+        })
+        .collect();
+    Ok((dataset, values))
+}
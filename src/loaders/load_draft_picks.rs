@@ -1,22 +1,59 @@
 use polars::prelude::*;
 
-use crate::downloader::download_nflverse;
+use crate::catalog::DRAFT_PICKS;
+use crate::downloader::{scan_nflverse, scan_nflverse_async};
 use crate::error::Result;
+use crate::loaders::frames::{
+    download_season_file, download_season_file_async, filter_seasons_lazy,
+};
+use crate::loaders::seasons::Seasons;
 
-/// Load draft pick data.
+/// Load draft pick data for the given seasons.
 ///
-/// If `seasons` is None, loads all seasons.
-/// Pass a vec of season years to filter to specific seasons.
-pub fn load_draft_picks(seasons: Option<Vec<i32>>) -> Result<DataFrame> {
-    let mut df = download_nflverse("draft_picks/draft_picks")?;
+/// The data is one file filtered by season; pass `Seasons::All` for every season.
+pub fn load_draft_picks(seasons: impl Into<Seasons>) -> Result<DataFrame> {
+    load_draft_picks_columns(seasons.into(), None)
+}
+
+/// Async version of [`load_draft_picks`].
+pub async fn load_draft_picks_async(seasons: impl Into<Seasons>) -> Result<DataFrame> {
+    load_draft_picks_columns_async(seasons.into(), None).await
+}
+
+/// [`load_draft_picks`], fetching only `columns` (plus `season`) where Range
+/// requests allow. Used by [`crate::load`], which selects them afterwards.
+pub(crate) fn load_draft_picks_columns(
+    seasons: Seasons,
+    columns: Option<&[&str]>,
+) -> Result<DataFrame> {
+    let seasons = DRAFT_PICKS.season_filter(&seasons)?;
+    download_season_file(&DRAFT_PICKS.path(&[])?, seasons, columns)
+}
+
+/// Async version of [`load_draft_picks_columns`].
+pub(crate) async fn load_draft_picks_columns_async(
+    seasons: Seasons,
+    columns: Option<&[&str]>,
+) -> Result<DataFrame> {
+    let seasons = DRAFT_PICKS.season_filter(&seasons)?;
+    download_season_file_async(&DRAFT_PICKS.path(&[])?, seasons, columns).await
+}
 
-    if let Some(season_list) = seasons {
-        let season_series = Series::new(PlSmallStr::from("seasons"), &season_list);
-        df = df
-            .lazy()
-            .filter(col("season").is_in(lit(season_series)))
-            .collect()?;
-    }
+/// Lazy version of [`load_draft_picks`], scanning the whole file and filtering it
+/// by season. See [`crate::downloader::scan_parquet`].
+pub fn scan_draft_picks(seasons: impl Into<Seasons>) -> Result<LazyFrame> {
+    let seasons = DRAFT_PICKS.season_filter(&seasons.into())?;
+    Ok(filter_seasons_lazy(
+        scan_nflverse(&DRAFT_PICKS.path(&[])?)?,
+        seasons,
+    ))
+}
 
-    Ok(df)
+/// Async version of [`scan_draft_picks`].
+pub async fn scan_draft_picks_async(seasons: impl Into<Seasons>) -> Result<LazyFrame> {
+    let seasons = DRAFT_PICKS.season_filter(&seasons.into())?;
+    Ok(filter_seasons_lazy(
+        scan_nflverse_async(&DRAFT_PICKS.path(&[])?).await?,
+        seasons,
+    ))
 }
@@ -1,102 +1,85 @@
 use polars::prelude::*;
 
-use crate::downloader::{DataFormat, Repository, build_url, download_dataframe};
-use crate::error::{NflReadError, Result};
-use crate::loaders::seasons::resolve_seasons;
+use crate::catalog::{Dataset, FF_OPPORTUNITY, FF_PLAYERIDS, FF_RANKINGS};
+use crate::downloader::{
+    DataFormat, build_url, download_all, download_all_async, download_dataframe,
+    download_dataframe_async,
+};
+use crate::error::Result;
+use crate::loaders::frames::{concat_frames, concat_frames_async};
+use crate::loaders::seasons::Seasons;
+use crate::params::{ModelVersion, OpportunityStatType, RankingType};
 
 /// Load fantasy football player IDs from dynastyprocess.
 pub fn load_ff_playerids() -> Result<DataFrame> {
-    let url = build_url(Repository::Dynastyprocess, "db_playerids.csv", DataFormat::Csv);
-    download_dataframe(&url, DataFormat::Csv)
+    let (url, format) = single_url(&FF_PLAYERIDS, &[])?;
+    download_dataframe(&url, format)
+}
+
+/// Async version of [`load_ff_playerids`].
+pub async fn load_ff_playerids_async() -> Result<DataFrame> {
+    let (url, format) = single_url(&FF_PLAYERIDS, &[])?;
+    download_dataframe_async(&url, format).await
 }
 
 /// Load fantasy football rankings from dynastyprocess.
 ///
-/// `ranking_type` can be: "draft", "week", or "all"
-pub fn load_ff_rankings(ranking_type: &str) -> Result<DataFrame> {
-    match ranking_type {
-        "draft" => {
-            let url = build_url(
-                Repository::Dynastyprocess,
-                "db_fpecr_latest.csv",
-                DataFormat::Csv,
-            );
-            download_dataframe(&url, DataFormat::Csv)
-        }
-        "week" => {
-            let url = build_url(
-                Repository::Dynastyprocess,
-                "fp_latest_weekly.csv",
-                DataFormat::Csv,
-            );
-            download_dataframe(&url, DataFormat::Csv)
-        }
-        "all" => {
-            let url = build_url(
-                Repository::Dynastyprocess,
-                "db_fpecr.parquet",
-                DataFormat::Parquet,
-            );
-            download_dataframe(&url, DataFormat::Parquet)
-        }
-        _ => Err(NflReadError::InvalidParameter(format!(
-            "Invalid ranking_type: '{ranking_type}'. Must be one of: draft, week, all"
-        ))),
-    }
+/// `ranking_type` picks current draft or weekly rankings, or all archived ones.
+pub fn load_ff_rankings(ranking_type: RankingType) -> Result<DataFrame> {
+    let (url, format) = single_url(&FF_RANKINGS, &[("ranking_type", ranking_type.as_str())])?;
+    download_dataframe(&url, format)
+}
+
+/// Async version of [`load_ff_rankings`].
+pub async fn load_ff_rankings_async(ranking_type: RankingType) -> Result<DataFrame> {
+    let (url, format) = single_url(&FF_RANKINGS, &[("ranking_type", ranking_type.as_str())])?;
+    download_dataframe_async(&url, format).await
+}
+
+/// URL and format of a single-file dataset outside nflverse-data.
+fn single_url(dataset: &Dataset, params: &[(&str, &str)]) -> Result<(String, DataFormat)> {
+    let file = dataset.file(params)?;
+    let path = file.path(params, None);
+    Ok((
+        build_url(dataset.repository, &path, file.format),
+        file.format,
+    ))
 }
 
 /// Load fantasy football opportunity data from ffopportunity.
 ///
-/// `stat_type` can be: "weekly", "pbp_pass", or "pbp_rush"
-/// `model_version` can be: "latest" or "v1.0.0"
+/// `stat_type` picks weekly totals or play-level passing or rushing data.
 pub fn load_ff_opportunity(
-    seasons: Option<Vec<i32>>,
-    stat_type: &str,
-    model_version: &str,
+    seasons: impl Into<Seasons>,
+    stat_type: OpportunityStatType,
+    model_version: ModelVersion,
 ) -> Result<DataFrame> {
-    validate_stat_type(stat_type)?;
-    validate_model_version(model_version)?;
-
-    let first_season = 2006;
-    let season_list = resolve_seasons(seasons, first_season)?;
-    let mut dfs = Vec::new();
-
-    for season in &season_list {
-        let path = format!("{model_version}-data/ep_{stat_type}_{season}");
-        let url = build_url(Repository::Ffopportunity, &path, DataFormat::Parquet);
-        let df = download_dataframe(&url, DataFormat::Parquet)?;
-        dfs.push(df);
-    }
-
-    if dfs.len() == 1 {
-        return Ok(dfs.into_iter().next().unwrap());
-    }
-
-    let lazy_frames: Vec<LazyFrame> = dfs.into_iter().map(|df| df.lazy()).collect();
-    Ok(concat(lazy_frames, UnionArgs {
-        parallel: true,
-        rechunk: true,
-        to_supertypes: true,
-        diagonal: true,
-        from_partitioned_ds: false,
-        maintain_order: true,
-    })?.collect()?)
+    let urls = opportunity_urls(&seasons.into(), stat_type, model_version)?;
+    concat_frames(download_all(&urls, DataFormat::Parquet)?)
 }
 
-fn validate_stat_type(stat_type: &str) -> Result<()> {
-    match stat_type {
-        "weekly" | "pbp_pass" | "pbp_rush" => Ok(()),
-        _ => Err(NflReadError::InvalidParameter(format!(
-            "Invalid stat_type: '{stat_type}'. Must be one of: weekly, pbp_pass, pbp_rush"
-        ))),
-    }
+/// Async version of [`load_ff_opportunity`].
+pub async fn load_ff_opportunity_async(
+    seasons: impl Into<Seasons>,
+    stat_type: OpportunityStatType,
+    model_version: ModelVersion,
+) -> Result<DataFrame> {
+    let urls = opportunity_urls(&seasons.into(), stat_type, model_version)?;
+    concat_frames_async(download_all_async(&urls, DataFormat::Parquet).await?).await
 }
 
-fn validate_model_version(version: &str) -> Result<()> {
-    match version {
-        "latest" | "v1.0.0" => Ok(()),
-        _ => Err(NflReadError::InvalidParameter(format!(
-            "Invalid model_version: '{version}'. Must be one of: latest, v1.0.0"
-        ))),
-    }
+fn opportunity_urls(
+    seasons: &Seasons,
+    stat_type: OpportunityStatType,
+    model_version: ModelVersion,
+) -> Result<Vec<String>> {
+    let params = [
+        ("stat_type", stat_type.as_str()),
+        ("model_version", model_version.as_str()),
+    ];
+    Ok(FF_OPPORTUNITY
+        .resolve_paths(&params, seasons)?
+        .iter()
+        .map(|path| build_url(FF_OPPORTUNITY.repository, path, DataFormat::Parquet))
+        .collect())
 }
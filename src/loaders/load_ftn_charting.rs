@@ -1,33 +1,42 @@
 use polars::prelude::*;
 
-use crate::downloader::download_nflverse;
+use crate::catalog::FTN_CHARTING;
+use crate::downloader::{scan_nflverse_all, scan_nflverse_all_async};
 use crate::error::Result;
-use crate::loaders::seasons::resolve_seasons;
-
-const FIRST_SEASON: i32 = 2022;
+use crate::loaders::frames::{concat_lazy, download_seasons, download_seasons_async};
+use crate::loaders::seasons::Seasons;
 
 /// Load FTN charting data for the given seasons.
-pub fn load_ftn_charting(seasons: Option<Vec<i32>>) -> Result<DataFrame> {
-    let season_list = resolve_seasons(seasons, FIRST_SEASON)?;
-    let mut dfs = Vec::new();
+///
+/// `columns`, if given, selects the columns to keep; only those are read
+/// from each season's file. They are checked against the most recent season
+/// before the others are downloaded.
+pub fn load_ftn_charting(
+    seasons: impl Into<Seasons>,
+    columns: Option<&[&str]>,
+) -> Result<DataFrame> {
+    let paths = FTN_CHARTING.resolve_paths(&[], &seasons.into())?;
+    download_seasons(FTN_CHARTING.name, &paths, columns)
+}
 
-    for season in &season_list {
-        let path = format!("ftn_charting/ftn_charting_{season}");
-        let df = download_nflverse(&path)?;
-        dfs.push(df);
-    }
+/// Async version of [`load_ftn_charting`].
+pub async fn load_ftn_charting_async(
+    seasons: impl Into<Seasons>,
+    columns: Option<&[&str]>,
+) -> Result<DataFrame> {
+    let paths = FTN_CHARTING.resolve_paths(&[], &seasons.into())?;
+    download_seasons_async(FTN_CHARTING.name, &paths, columns).await
+}
 
-    if dfs.len() == 1 {
-        return Ok(dfs.into_iter().next().unwrap());
-    }
+/// Lazy version of [`load_ftn_charting`]. See
+/// [`crate::downloader::scan_parquet`] for how the files are fetched and read.
+pub fn scan_ftn_charting(seasons: impl Into<Seasons>) -> Result<LazyFrame> {
+    let paths = FTN_CHARTING.resolve_paths(&[], &seasons.into())?;
+    concat_lazy(scan_nflverse_all(&paths)?)
+}
 
-    let lazy_frames: Vec<LazyFrame> = dfs.into_iter().map(|df| df.lazy()).collect();
-    Ok(concat(lazy_frames, UnionArgs {
-        parallel: true,
-        rechunk: true,
-        to_supertypes: true,
-        diagonal: true,
-        from_partitioned_ds: false,
-        maintain_order: true,
-    })?.collect()?)
+/// Async version of [`scan_ftn_charting`].
+pub async fn scan_ftn_charting_async(seasons: impl Into<Seasons>) -> Result<LazyFrame> {
+    let paths = FTN_CHARTING.resolve_paths(&[], &seasons.into())?;
+    concat_lazy(scan_nflverse_all_async(&paths).await?)
 }
@@ -1,48 +1,81 @@
 use polars::prelude::*;
 
-use crate::downloader::download_nflverse;
-use crate::error::{NflReadError, Result};
-
-const FIRST_SEASON: i32 = 2016;
+use crate::catalog::NEXTGEN_STATS;
+use crate::downloader::{scan_nflverse, scan_nflverse_async};
+use crate::error::Result;
+use crate::loaders::frames::{
+    download_season_file, download_season_file_async, filter_seasons_lazy,
+};
+use crate::loaders::seasons::Seasons;
+use crate::params::NgsStatType;
 
 /// Load Next Gen Stats data.
 ///
-/// `stat_type` can be: "passing", "receiving", or "rushing"
-/// `seasons` filters to specific seasons after download (single file per stat type).
+/// `stat_type` picks the passing, receiving or rushing table.
+/// `seasons` filters to specific seasons (single file per stat type, read with
+/// HTTP Range requests where possible); pass `Seasons::All` for every season.
 pub fn load_nextgen_stats(
-    seasons: Option<Vec<i32>>,
-    stat_type: &str,
+    seasons: impl Into<Seasons>,
+    stat_type: NgsStatType,
+) -> Result<DataFrame> {
+    load_nextgen_stats_columns(seasons.into(), stat_type, None)
+}
+
+/// Async version of [`load_nextgen_stats`].
+pub async fn load_nextgen_stats_async(
+    seasons: impl Into<Seasons>,
+    stat_type: NgsStatType,
+) -> Result<DataFrame> {
+    load_nextgen_stats_columns_async(seasons.into(), stat_type, None).await
+}
+
+/// [`load_nextgen_stats`], fetching only `columns` (plus `season`) where
+/// Range requests allow. Used by [`crate::load`], which selects them
+/// afterwards.
+pub(crate) fn load_nextgen_stats_columns(
+    seasons: Seasons,
+    stat_type: NgsStatType,
+    columns: Option<&[&str]>,
 ) -> Result<DataFrame> {
-    validate_stat_type(stat_type)?;
-
-    let path = format!("nextgen_stats/ngs_{stat_type}");
-    let mut df = download_nflverse(&path)?;
-
-    // Filter by season if specified
-    if let Some(season_list) = seasons {
-        // Validate seasons
-        for s in &season_list {
-            if *s < FIRST_SEASON {
-                return Err(NflReadError::InvalidSeason(format!(
-                    "Season {s} is before first available season ({FIRST_SEASON})"
-                )));
-            }
-        }
-        let season_series = Series::new(PlSmallStr::from("seasons"), &season_list);
-        df = df
-            .lazy()
-            .filter(col("season").is_in(lit(season_series)))
-            .collect()?;
-    }
-
-    Ok(df)
+    let (path, seasons) = season_path(stat_type, &seasons)?;
+    download_season_file(&path, seasons, columns)
+}
+
+/// Async version of [`load_nextgen_stats_columns`].
+pub(crate) async fn load_nextgen_stats_columns_async(
+    seasons: Seasons,
+    stat_type: NgsStatType,
+    columns: Option<&[&str]>,
+) -> Result<DataFrame> {
+    let (path, seasons) = season_path(stat_type, &seasons)?;
+    download_season_file_async(&path, seasons, columns).await
+}
+
+/// Lazy version of [`load_nextgen_stats`], scanning the whole file for the
+/// stat type and filtering it by season. See [`crate::downloader::scan_parquet`].
+pub fn scan_nextgen_stats(
+    seasons: impl Into<Seasons>,
+    stat_type: NgsStatType,
+) -> Result<LazyFrame> {
+    let (path, seasons) = season_path(stat_type, &seasons.into())?;
+    Ok(filter_seasons_lazy(scan_nflverse(&path)?, seasons))
+}
+
+/// Async version of [`scan_nextgen_stats`].
+pub async fn scan_nextgen_stats_async(
+    seasons: impl Into<Seasons>,
+    stat_type: NgsStatType,
+) -> Result<LazyFrame> {
+    let (path, seasons) = season_path(stat_type, &seasons.into())?;
+    Ok(filter_seasons_lazy(
+        scan_nflverse_async(&path).await?,
+        seasons,
+    ))
 }
 
-fn validate_stat_type(stat_type: &str) -> Result<()> {
-    match stat_type {
-        "passing" | "receiving" | "rushing" => Ok(()),
-        _ => Err(NflReadError::InvalidParameter(format!(
-            "Invalid stat_type: '{stat_type}'. Must be one of: passing, receiving, rushing"
-        ))),
-    }
+fn season_path(stat_type: NgsStatType, seasons: &Seasons) -> Result<(String, Option<Vec<i32>>)> {
+    let params = [("stat_type", stat_type.as_str())];
+    NEXTGEN_STATS.validate_params(&params)?;
+    let seasons = NEXTGEN_STATS.season_filter(seasons)?;
+    Ok((NEXTGEN_STATS.path(&params)?, seasons))
 }
@@ -1,22 +1,59 @@
 use polars::prelude::*;
 
-use crate::downloader::download_nflverse;
+use crate::catalog::OFFICIALS;
+use crate::downloader::{scan_nflverse, scan_nflverse_async};
 use crate::error::Result;
+use crate::loaders::frames::{
+    download_season_file, download_season_file_async, filter_seasons_lazy,
+};
+use crate::loaders::seasons::Seasons;
 
-/// Load officials data.
+/// Load officials data for the given seasons.
 ///
-/// If `seasons` is None, loads all seasons.
-/// Pass a vec of season years to filter to specific seasons.
-pub fn load_officials(seasons: Option<Vec<i32>>) -> Result<DataFrame> {
-    let mut df = download_nflverse("officials/officials")?;
+/// The data is one file filtered by season; pass `Seasons::All` for every season.
+pub fn load_officials(seasons: impl Into<Seasons>) -> Result<DataFrame> {
+    load_officials_columns(seasons.into(), None)
+}
+
+/// Async version of [`load_officials`].
+pub async fn load_officials_async(seasons: impl Into<Seasons>) -> Result<DataFrame> {
+    load_officials_columns_async(seasons.into(), None).await
+}
+
+/// [`load_officials`], fetching only `columns` (plus `season`) where Range
+/// requests allow. Used by [`crate::load`], which selects them afterwards.
+pub(crate) fn load_officials_columns(
+    seasons: Seasons,
+    columns: Option<&[&str]>,
+) -> Result<DataFrame> {
+    let seasons = OFFICIALS.season_filter(&seasons)?;
+    download_season_file(&OFFICIALS.path(&[])?, seasons, columns)
+}
+
+/// Async version of [`load_officials_columns`].
+pub(crate) async fn load_officials_columns_async(
+    seasons: Seasons,
+    columns: Option<&[&str]>,
+) -> Result<DataFrame> {
+    let seasons = OFFICIALS.season_filter(&seasons)?;
+    download_season_file_async(&OFFICIALS.path(&[])?, seasons, columns).await
+}
 
-    if let Some(season_list) = seasons {
-        let season_series = Series::new(PlSmallStr::from("seasons"), &season_list);
-        df = df
-            .lazy()
-            .filter(col("season").is_in(lit(season_series)))
-            .collect()?;
-    }
+/// Lazy version of [`load_officials`], scanning the whole file and filtering it
+/// by season. See [`crate::downloader::scan_parquet`].
+pub fn scan_officials(seasons: impl Into<Seasons>) -> Result<LazyFrame> {
+    let seasons = OFFICIALS.season_filter(&seasons.into())?;
+    Ok(filter_seasons_lazy(
+        scan_nflverse(&OFFICIALS.path(&[])?)?,
+        seasons,
+    ))
+}
 
-    Ok(df)
+/// Async version of [`scan_officials`].
+pub async fn scan_officials_async(seasons: impl Into<Seasons>) -> Result<LazyFrame> {
+    let seasons = OFFICIALS.season_filter(&seasons.into())?;
+    Ok(filter_seasons_lazy(
+        scan_nflverse_async(&OFFICIALS.path(&[])?).await?,
+        seasons,
+    ))
 }
@@ -1,51 +1,68 @@
 use polars::prelude::*;
 
-use crate::downloader::download_nflverse;
-use crate::error::Result;
-use crate::loaders::seasons::resolve_seasons;
-use crate::utils_date::{get_current_season, get_current_week};
-
-const FIRST_SEASON: i32 = 2016;
+use crate::catalog::PARTICIPATION;
+use crate::downloader::{scan_nflverse_all, scan_nflverse_all_async};
+use crate::error::{NflReadError, Result};
+use crate::loaders::frames::{concat_lazy, download_seasons, download_seasons_async};
+use crate::loaders::seasons::Seasons;
+use crate::utils_date::{get_current_season, get_current_week, get_current_week_async};
 
 /// Load participation data for the given seasons.
 ///
 /// Participation data is only available for completed seasons
-/// (unless it is the final week 22 of the season).
-pub fn load_participation(seasons: Option<Vec<i32>>) -> Result<DataFrame> {
+/// (unless it is the final week 22 of the season); seasons still in progress,
+/// such as the default `Seasons::Current`, are skipped.
+///
+/// `columns`, if given, selects the columns to keep; only those are read
+/// from each season's file. They are checked against the most recent season
+/// before the others are downloaded.
+pub fn load_participation(
+    seasons: impl Into<Seasons>,
+    columns: Option<&[&str]>,
+) -> Result<DataFrame> {
+    let current_week = get_current_week(false).unwrap_or(1);
+    let paths = season_paths(&seasons.into(), current_week)?;
+    download_seasons(PARTICIPATION.name, &paths, columns)
+}
+
+/// Async version of [`load_participation`].
+pub async fn load_participation_async(
+    seasons: impl Into<Seasons>,
+    columns: Option<&[&str]>,
+) -> Result<DataFrame> {
+    let current_week = get_current_week_async(false).await.unwrap_or(1);
+    let paths = season_paths(&seasons.into(), current_week)?;
+    download_seasons_async(PARTICIPATION.name, &paths, columns).await
+}
+
+/// Lazy version of [`load_participation`], with pushdown into the
+/// per-season participation files. See [`crate::downloader::scan_parquet`].
+pub fn scan_participation(seasons: impl Into<Seasons>) -> Result<LazyFrame> {
     let current_week = get_current_week(false).unwrap_or(1);
+    let paths = season_paths(&seasons.into(), current_week)?;
+    concat_lazy(scan_nflverse_all(&paths)?)
+}
+
+/// Async version of [`scan_participation`].
+pub async fn scan_participation_async(seasons: impl Into<Seasons>) -> Result<LazyFrame> {
+    let current_week = get_current_week_async(false).await.unwrap_or(1);
+    let paths = season_paths(&seasons.into(), current_week)?;
+    concat_lazy(scan_nflverse_all_async(&paths).await?)
+}
+
+fn season_paths(seasons: &Seasons, current_week: i32) -> Result<Vec<String>> {
     let max_season = if current_week == 22 {
         get_current_season(false)
     } else {
         get_current_season(false) - 1
     };
 
-    let season_list = resolve_seasons(seasons, FIRST_SEASON)?;
-    let mut dfs = Vec::new();
-
-    for season in &season_list {
-        if *season > max_season {
-            continue;
-        }
-        let path = format!("pbp_participation/pbp_participation_{season}");
-        let df = download_nflverse(&path)?;
-        dfs.push(df);
-    }
-
-    if dfs.is_empty() {
-        return Err(crate::error::NflReadError::NoData);
-    }
+    let seasons = PARTICIPATION.resolve_seasons(seasons)?;
+    let completed: Vec<i32> = seasons.into_iter().filter(|s| *s <= max_season).collect();
+    let paths = PARTICIPATION.paths(&[], &completed)?;
 
-    if dfs.len() == 1 {
-        return Ok(dfs.into_iter().next().unwrap());
+    if paths.is_empty() {
+        return Err(NflReadError::NoData);
     }
-
-    let lazy_frames: Vec<LazyFrame> = dfs.into_iter().map(|df| df.lazy()).collect();
-    Ok(concat(lazy_frames, UnionArgs {
-        parallel: true,
-        rechunk: true,
-        to_supertypes: true,
-        diagonal: true,
-        from_partitioned_ds: false,
-        maintain_order: true,
-    })?.collect()?)
+    Ok(paths)
 }
@@ -1,41 +1,43 @@
 use polars::prelude::*;
 
-use crate::downloader::download_nflverse;
+use crate::catalog::PBP;
+use crate::downloader::{scan_nflverse_all, scan_nflverse_all_async};
 use crate::error::Result;
-use crate::loaders::seasons::resolve_seasons;
-
-const FIRST_SEASON: i32 = 1999;
+use crate::loaders::frames::{concat_lazy, download_seasons, download_seasons_async};
+use crate::loaders::seasons::Seasons;
 
 /// Load play-by-play data for the given seasons.
 ///
-/// If `seasons` is None, loads the current season.
-/// Pass a vec of season years to load specific seasons.
-pub fn load_pbp(seasons: Option<Vec<i32>>) -> Result<DataFrame> {
-    let season_list = resolve_seasons(seasons, FIRST_SEASON)?;
-    let mut dfs = Vec::new();
-
-    for season in &season_list {
-        let path = format!("pbp/play_by_play_{season}");
-        let df = download_nflverse(&path)?;
-        dfs.push(df);
-    }
+/// `seasons` is anything convertible into [`Seasons`], such as `2023`,
+/// `vec![2022, 2023]`, `2020..=2023` or `Seasons::Last(3)`.
+///
+/// `columns`, if given, selects the columns to keep; only those are read
+/// from each season's file. They are checked against the most recent season
+/// before the others are downloaded.
+pub fn load_pbp(seasons: impl Into<Seasons>, columns: Option<&[&str]>) -> Result<DataFrame> {
+    let paths = PBP.resolve_paths(&[], &seasons.into())?;
+    download_seasons(PBP.name, &paths, columns)
+}
 
-    if dfs.len() == 1 {
-        return Ok(dfs.into_iter().next().unwrap());
-    }
+/// Async version of [`load_pbp`].
+pub async fn load_pbp_async(
+    seasons: impl Into<Seasons>,
+    columns: Option<&[&str]>,
+) -> Result<DataFrame> {
+    let paths = PBP.resolve_paths(&[], &seasons.into())?;
+    download_seasons_async(PBP.name, &paths, columns).await
+}
 
-    let refs: Vec<&DataFrame> = dfs.iter().collect();
-    Ok(concat_df_diagonal(&refs)?)
+/// Lazy version of [`load_pbp`]. Each season's file is scanned rather than
+/// decoded, so selecting a few of its ~370 columns reads only those. See
+/// [`crate::downloader::scan_parquet`].
+pub fn scan_pbp(seasons: impl Into<Seasons>) -> Result<LazyFrame> {
+    let paths = PBP.resolve_paths(&[], &seasons.into())?;
+    concat_lazy(scan_nflverse_all(&paths)?)
 }
 
-fn concat_df_diagonal(dfs: &[&DataFrame]) -> std::result::Result<DataFrame, PolarsError> {
-    let lazy_frames: Vec<LazyFrame> = dfs.iter().map(|df| (*df).clone().lazy()).collect();
-    concat(lazy_frames, UnionArgs {
-        parallel: true,
-        rechunk: true,
-        to_supertypes: true,
-        diagonal: true,
-        from_partitioned_ds: false,
-        maintain_order: true,
-    })?.collect()
+/// Async version of [`scan_pbp`].
+pub async fn scan_pbp_async(seasons: impl Into<Seasons>) -> Result<LazyFrame> {
+    let paths = PBP.resolve_paths(&[], &seasons.into())?;
+    concat_lazy(scan_nflverse_all_async(&paths).await?)
 }
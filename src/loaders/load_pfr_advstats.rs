@@ -1,94 +1,125 @@
 use polars::prelude::*;
 
-use crate::downloader::download_nflverse;
-use crate::error::{NflReadError, Result};
-use crate::loaders::seasons::resolve_seasons;
-
-const FIRST_SEASON: i32 = 2018;
+use crate::catalog::PFR_ADVSTATS;
+use crate::downloader::{scan_nflverse_all, scan_nflverse_all_async};
+use crate::error::Result;
+use crate::loaders::frames::{
+    concat_lazy, download_season_file, download_season_file_async, download_seasons,
+    download_seasons_async, filter_seasons_lazy,
+};
+use crate::loaders::seasons::Seasons;
+use crate::params::{PfrStatType, PfrSummaryLevel};
 
 /// Load Pro Football Reference advanced stats.
 ///
-/// `stat_type` can be: "pass", "rush", "rec", or "def"
-/// `summary_level` can be: "week" or "season"
+/// `stat_type` picks the passing, rushing, receiving or defense table.
 ///
-/// For "week" level, downloads one file per season.
-/// For "season" level, downloads a single combined file and filters by season.
+/// At the `Week` level, downloads one file per season.
+/// At the `Season` level, reads the matching seasons from a single combined file;
+/// pass `Seasons::All` for every season.
 pub fn load_pfr_advstats(
-    seasons: Option<Vec<i32>>,
-    stat_type: &str,
-    summary_level: &str,
+    seasons: impl Into<Seasons>,
+    stat_type: PfrStatType,
+    summary_level: PfrSummaryLevel,
 ) -> Result<DataFrame> {
-    validate_stat_type(stat_type)?;
-    validate_summary_level(summary_level)?;
-
-    match summary_level {
-        "week" => load_weekly(seasons, stat_type),
-        "season" => load_season(seasons, stat_type),
-        _ => unreachable!(),
-    }
+    load_pfr_advstats_columns(seasons.into(), stat_type, summary_level, None)
 }
 
-fn load_weekly(seasons: Option<Vec<i32>>, stat_type: &str) -> Result<DataFrame> {
-    let season_list = resolve_seasons(seasons, FIRST_SEASON)?;
-    let mut dfs = Vec::new();
-
-    for season in &season_list {
-        let path = format!("pfr_advstats/advstats_week_{stat_type}_{season}");
-        let df = download_nflverse(&path)?;
-        dfs.push(df);
-    }
+/// Async version of [`load_pfr_advstats`].
+pub async fn load_pfr_advstats_async(
+    seasons: impl Into<Seasons>,
+    stat_type: PfrStatType,
+    summary_level: PfrSummaryLevel,
+) -> Result<DataFrame> {
+    load_pfr_advstats_columns_async(seasons.into(), stat_type, summary_level, None).await
+}
 
-    if dfs.len() == 1 {
-        return Ok(dfs.into_iter().next().unwrap());
+/// [`load_pfr_advstats`], reading only `columns` (plus `season` from the
+/// combined file) where the source allows. Used by [`crate::load`], which
+/// selects them afterwards.
+pub(crate) fn load_pfr_advstats_columns(
+    seasons: Seasons,
+    stat_type: PfrStatType,
+    summary_level: PfrSummaryLevel,
+    columns: Option<&[&str]>,
+) -> Result<DataFrame> {
+    let params = [
+        ("stat_type", stat_type.as_str()),
+        ("summary_level", summary_level.as_str()),
+    ];
+    let paths = PFR_ADVSTATS.resolve_paths(&params, &seasons)?;
+    match summary_level {
+        PfrSummaryLevel::Week => download_seasons(PFR_ADVSTATS.name, &paths, columns),
+        PfrSummaryLevel::Season => {
+            let seasons = PFR_ADVSTATS.season_filter(&seasons)?;
+            download_season_file(&paths[0], seasons, columns)
+        }
     }
-
-    let lazy_frames: Vec<LazyFrame> = dfs.into_iter().map(|df| df.lazy()).collect();
-    Ok(concat(lazy_frames, UnionArgs {
-        parallel: true,
-        rechunk: true,
-        to_supertypes: true,
-        diagonal: true,
-        from_partitioned_ds: false,
-        maintain_order: true,
-    })?.collect()?)
 }
 
-fn load_season(seasons: Option<Vec<i32>>, stat_type: &str) -> Result<DataFrame> {
-    let path = format!("pfr_advstats/advstats_season_{stat_type}");
-    let mut df = download_nflverse(&path)?;
-
-    if let Some(season_list) = seasons {
-        for s in &season_list {
-            if *s < FIRST_SEASON {
-                return Err(NflReadError::InvalidSeason(format!(
-                    "Season {s} is before first available season ({FIRST_SEASON})"
-                )));
-            }
+/// Async version of [`load_pfr_advstats_columns`].
+pub(crate) async fn load_pfr_advstats_columns_async(
+    seasons: Seasons,
+    stat_type: PfrStatType,
+    summary_level: PfrSummaryLevel,
+    columns: Option<&[&str]>,
+) -> Result<DataFrame> {
+    let params = [
+        ("stat_type", stat_type.as_str()),
+        ("summary_level", summary_level.as_str()),
+    ];
+    let paths = PFR_ADVSTATS.resolve_paths(&params, &seasons)?;
+    match summary_level {
+        PfrSummaryLevel::Week => download_seasons_async(PFR_ADVSTATS.name, &paths, columns).await,
+        PfrSummaryLevel::Season => {
+            let seasons = PFR_ADVSTATS.season_filter(&seasons)?;
+            download_season_file_async(&paths[0], seasons, columns).await
         }
-        let season_series = Series::new(PlSmallStr::from("seasons"), &season_list);
-        df = df
-            .lazy()
-            .filter(col("season").is_in(lit(season_series)))
-            .collect()?;
     }
-
-    Ok(df)
 }
 
-fn validate_stat_type(stat_type: &str) -> Result<()> {
-    match stat_type {
-        "pass" | "rush" | "rec" | "def" => Ok(()),
-        _ => Err(NflReadError::InvalidParameter(format!(
-            "Invalid stat_type: '{stat_type}'. Must be one of: pass, rush, rec, def"
-        ))),
+/// Lazy version of [`load_pfr_advstats`]. At the `Season` level the whole
+/// combined file is scanned and filtered by season. See
+/// [`crate::downloader::scan_parquet`].
+pub fn scan_pfr_advstats(
+    seasons: impl Into<Seasons>,
+    stat_type: PfrStatType,
+    summary_level: PfrSummaryLevel,
+) -> Result<LazyFrame> {
+    let params = [
+        ("stat_type", stat_type.as_str()),
+        ("summary_level", summary_level.as_str()),
+    ];
+    let seasons = seasons.into();
+    let paths = PFR_ADVSTATS.resolve_paths(&params, &seasons)?;
+    let lf = concat_lazy(scan_nflverse_all(&paths)?)?;
+    match summary_level {
+        PfrSummaryLevel::Week => Ok(lf),
+        PfrSummaryLevel::Season => Ok(filter_seasons_lazy(
+            lf,
+            PFR_ADVSTATS.season_filter(&seasons)?,
+        )),
     }
 }
 
-fn validate_summary_level(level: &str) -> Result<()> {
-    match level {
-        "week" | "season" => Ok(()),
-        _ => Err(NflReadError::InvalidParameter(format!(
-            "Invalid summary_level: '{level}'. Must be one of: week, season"
-        ))),
+/// Async version of [`scan_pfr_advstats`].
+pub async fn scan_pfr_advstats_async(
+    seasons: impl Into<Seasons>,
+    stat_type: PfrStatType,
+    summary_level: PfrSummaryLevel,
+) -> Result<LazyFrame> {
+    let params = [
+        ("stat_type", stat_type.as_str()),
+        ("summary_level", summary_level.as_str()),
+    ];
+    let seasons = seasons.into();
+    let paths = PFR_ADVSTATS.resolve_paths(&params, &seasons)?;
+    let lf = concat_lazy(scan_nflverse_all_async(&paths).await?)?;
+    match summary_level {
+        PfrSummaryLevel::Week => Ok(lf),
+        PfrSummaryLevel::Season => Ok(filter_seasons_lazy(
+            lf,
+            PFR_ADVSTATS.season_filter(&seasons)?,
+        )),
     }
 }
@@ -1,49 +1,66 @@
 use polars::prelude::*;
 
-use crate::downloader::download_nflverse;
-use crate::error::{NflReadError, Result};
-use crate::loaders::seasons::resolve_seasons;
-
-const FIRST_SEASON: i32 = 1999;
+use crate::catalog::PLAYER_STATS;
+use crate::downloader::{scan_nflverse_all, scan_nflverse_all_async};
+use crate::error::Result;
+use crate::loaders::frames::{concat_lazy, download_seasons, download_seasons_async};
+use crate::loaders::seasons::Seasons;
+use crate::params::SummaryLevel;
 
 /// Load player stats for the given seasons.
 ///
-/// `summary_level` can be: "week", "reg", "post", or "reg+post"
+/// `summary_level` picks weekly rows or regular season, postseason or
+/// combined totals.
+///
+/// `columns`, if given, selects the columns to keep; only those are read
+/// from each season's file. They are checked against the most recent season
+/// before the others are downloaded.
 pub fn load_player_stats(
-    seasons: Option<Vec<i32>>,
-    summary_level: &str,
+    seasons: impl Into<Seasons>,
+    summary_level: SummaryLevel,
+    columns: Option<&[&str]>,
 ) -> Result<DataFrame> {
-    validate_summary_level(summary_level)?;
-    let level_str = summary_level.replace('+', "");
-    let season_list = resolve_seasons(seasons, FIRST_SEASON)?;
-    let mut dfs = Vec::new();
-
-    for season in &season_list {
-        let path = format!("stats_player/stats_player_{level_str}_{season}");
-        let df = download_nflverse(&path)?;
-        dfs.push(df);
-    }
+    let paths = PLAYER_STATS.resolve_paths(
+        &[("summary_level", summary_level.as_str())],
+        &seasons.into(),
+    )?;
+    download_seasons(PLAYER_STATS.name, &paths, columns)
+}
 
-    if dfs.len() == 1 {
-        return Ok(dfs.into_iter().next().unwrap());
-    }
+/// Async version of [`load_player_stats`].
+pub async fn load_player_stats_async(
+    seasons: impl Into<Seasons>,
+    summary_level: SummaryLevel,
+    columns: Option<&[&str]>,
+) -> Result<DataFrame> {
+    let paths = PLAYER_STATS.resolve_paths(
+        &[("summary_level", summary_level.as_str())],
+        &seasons.into(),
+    )?;
+    download_seasons_async(PLAYER_STATS.name, &paths, columns).await
+}
 
-    let lazy_frames: Vec<LazyFrame> = dfs.into_iter().map(|df| df.lazy()).collect();
-    Ok(concat(lazy_frames, UnionArgs {
-        parallel: true,
-        rechunk: true,
-        to_supertypes: true,
-        diagonal: true,
-        from_partitioned_ds: false,
-        maintain_order: true,
-    })?.collect()?)
+/// Lazy version of [`load_player_stats`], scanning each season's file for
+/// the summary level. See [`crate::downloader::scan_parquet`].
+pub fn scan_player_stats(
+    seasons: impl Into<Seasons>,
+    summary_level: SummaryLevel,
+) -> Result<LazyFrame> {
+    let paths = PLAYER_STATS.resolve_paths(
+        &[("summary_level", summary_level.as_str())],
+        &seasons.into(),
+    )?;
+    concat_lazy(scan_nflverse_all(&paths)?)
 }
 
-fn validate_summary_level(level: &str) -> Result<()> {
-    match level {
-        "week" | "reg" | "post" | "reg+post" => Ok(()),
-        _ => Err(NflReadError::InvalidParameter(format!(
-            "Invalid summary_level: '{level}'. Must be one of: week, reg, post, reg+post"
-        ))),
-    }
+/// Async version of [`scan_player_stats`].
+pub async fn scan_player_stats_async(
+    seasons: impl Into<Seasons>,
+    summary_level: SummaryLevel,
+) -> Result<LazyFrame> {
+    let paths = PLAYER_STATS.resolve_paths(
+        &[("summary_level", summary_level.as_str())],
+        &seasons.into(),
+    )?;
+    concat_lazy(scan_nflverse_all_async(&paths).await?)
 }
@@ -1,9 +1,27 @@
-use polars::prelude::DataFrame;
+use polars::prelude::{DataFrame, LazyFrame};
 
-use crate::downloader::download_nflverse;
+use crate::catalog::PLAYERS;
+use crate::downloader::{
+    download_nflverse, download_nflverse_async, scan_nflverse, scan_nflverse_async,
+};
 use crate::error::Result;
 
 /// Load player information. No season parameter needed.
 pub fn load_players() -> Result<DataFrame> {
-    download_nflverse("players/players")
+    download_nflverse(&PLAYERS.path(&[])?)
+}
+
+/// Async version of [`load_players`].
+pub async fn load_players_async() -> Result<DataFrame> {
+    download_nflverse_async(&PLAYERS.path(&[])?).await
+}
+
+/// Lazy version of [`load_players`]. See [`crate::downloader::scan_parquet`].
+pub fn scan_players() -> Result<LazyFrame> {
+    scan_nflverse(&PLAYERS.path(&[])?)
+}
+
+/// Async version of [`scan_players`].
+pub async fn scan_players_async() -> Result<LazyFrame> {
+    scan_nflverse_async(&PLAYERS.path(&[])?).await
 }
@@ -1,35 +1,35 @@
 use polars::prelude::*;
 
-use crate::downloader::download_nflverse;
+use crate::catalog::ROSTERS;
+use crate::downloader::{
+    download_nflverse_all, download_nflverse_all_async, scan_nflverse_all, scan_nflverse_all_async,
+};
 use crate::error::Result;
-use crate::loaders::seasons::resolve_seasons_roster;
-
-const FIRST_SEASON: i32 = 1920;
+use crate::loaders::frames::{concat_frames, concat_frames_async, concat_lazy};
+use crate::loaders::seasons::Seasons;
 
 /// Load roster data for the given seasons.
 ///
 /// Uses the roster cutoff (March 15) for determining the current season.
-pub fn load_rosters(seasons: Option<Vec<i32>>) -> Result<DataFrame> {
-    let season_list = resolve_seasons_roster(seasons, FIRST_SEASON)?;
-    let mut dfs = Vec::new();
+pub fn load_rosters(seasons: impl Into<Seasons>) -> Result<DataFrame> {
+    let paths = ROSTERS.resolve_paths(&[], &seasons.into())?;
+    concat_frames(download_nflverse_all(&paths)?)
+}
 
-    for season in &season_list {
-        let path = format!("rosters/roster_{season}");
-        let df = download_nflverse(&path)?;
-        dfs.push(df);
-    }
+/// Async version of [`load_rosters`].
+pub async fn load_rosters_async(seasons: impl Into<Seasons>) -> Result<DataFrame> {
+    let paths = ROSTERS.resolve_paths(&[], &seasons.into())?;
+    concat_frames_async(download_nflverse_all_async(&paths).await?).await
+}
 
-    if dfs.len() == 1 {
-        return Ok(dfs.into_iter().next().unwrap());
-    }
+/// Lazy version of [`load_rosters`]. See [`crate::downloader::scan_parquet`].
+pub fn scan_rosters(seasons: impl Into<Seasons>) -> Result<LazyFrame> {
+    let paths = ROSTERS.resolve_paths(&[], &seasons.into())?;
+    concat_lazy(scan_nflverse_all(&paths)?)
+}
 
-    let lazy_frames: Vec<LazyFrame> = dfs.into_iter().map(|df| df.lazy()).collect();
-    Ok(concat(lazy_frames, UnionArgs {
-        parallel: true,
-        rechunk: true,
-        to_supertypes: true,
-        diagonal: true,
-        from_partitioned_ds: false,
-        maintain_order: true,
-    })?.collect()?)
+/// Async version of [`scan_rosters`].
+pub async fn scan_rosters_async(seasons: impl Into<Seasons>) -> Result<LazyFrame> {
+    let paths = ROSTERS.resolve_paths(&[], &seasons.into())?;
+    concat_lazy(scan_nflverse_all_async(&paths).await?)
 }
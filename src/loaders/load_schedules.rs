@@ -1,38 +1,73 @@
 use polars::prelude::*;
 
-use crate::downloader::download_nflverse;
+use crate::catalog::SCHEDULES;
+use crate::downloader::{blocking, scan_nflverse, scan_nflverse_async};
 use crate::error::Result;
+use crate::loaders::frames::{
+    download_season_file, download_season_file_async, filter_seasons_lazy,
+};
+use crate::loaders::seasons::Seasons;
 
-/// Load schedule data.
+/// Load schedule data for the given seasons.
 ///
-/// If `seasons` is None, loads all seasons.
-/// Pass a vec of season years to filter to specific seasons.
-pub fn load_schedules(seasons: Option<Vec<i32>>) -> Result<DataFrame> {
-    let mut df = download_nflverse("schedules/games")?;
+/// The data is one file filtered by season; pass `Seasons::All` for every season.
+pub fn load_schedules(seasons: impl Into<Seasons>) -> Result<DataFrame> {
+    load_schedules_columns(seasons.into(), None)
+}
 
-    // Clean roof values
-    let valid_roof = &["dome", "outdoors", "closed", "open"];
-    if df.get_column_names().contains(&&PlSmallStr::from("roof")) {
-        df = df
-            .lazy()
-            .with_columns([when(col("roof").is_in(lit(Series::new(
-                PlSmallStr::from("roof"),
-                valid_roof,
-            ))))
-            .then(col("roof"))
-            .otherwise(lit(NULL))
-            .alias("roof")])
-            .collect()?;
-    }
+/// Async version of [`load_schedules`].
+pub async fn load_schedules_async(seasons: impl Into<Seasons>) -> Result<DataFrame> {
+    load_schedules_columns_async(seasons.into(), None).await
+}
 
-    // Filter by season if specified
-    if let Some(season_list) = seasons {
-        let season_series = Series::new(PlSmallStr::from("seasons"), &season_list);
-        df = df
-            .lazy()
-            .filter(col("season").is_in(lit(season_series)))
-            .collect()?;
-    }
+/// [`load_schedules`], fetching only `columns` (plus `season`) where Range
+/// requests allow. Used by [`crate::load`], which selects them afterwards.
+pub(crate) fn load_schedules_columns(
+    seasons: Seasons,
+    columns: Option<&[&str]>,
+) -> Result<DataFrame> {
+    let seasons = SCHEDULES.season_filter(&seasons)?;
+    let df = download_season_file(&SCHEDULES.path(&[])?, seasons, columns)?;
+    Ok(clean_roof(df.lazy())?.collect()?)
+}
+
+/// Async version of [`load_schedules_columns`].
+pub(crate) async fn load_schedules_columns_async(
+    seasons: Seasons,
+    columns: Option<&[&str]>,
+) -> Result<DataFrame> {
+    let seasons = SCHEDULES.season_filter(&seasons)?;
+    let df = download_season_file_async(&SCHEDULES.path(&[])?, seasons, columns).await?;
+    blocking(move || Ok(clean_roof(df.lazy())?.collect()?)).await
+}
+
+/// Lazy version of [`load_schedules`], scanning the whole file and filtering
+/// it by season. See [`crate::downloader::scan_parquet`].
+pub fn scan_schedules(seasons: impl Into<Seasons>) -> Result<LazyFrame> {
+    let seasons = SCHEDULES.season_filter(&seasons.into())?;
+    clean_roof(filter_seasons_lazy(
+        scan_nflverse(&SCHEDULES.path(&[])?)?,
+        seasons,
+    ))
+}
 
-    Ok(df)
+/// Async version of [`scan_schedules`].
+pub async fn scan_schedules_async(seasons: impl Into<Seasons>) -> Result<LazyFrame> {
+    let seasons = SCHEDULES.season_filter(&seasons.into())?;
+    let lf = scan_nflverse_async(&SCHEDULES.path(&[])?).await?;
+    clean_roof(filter_seasons_lazy(lf, seasons))
+}
+
+/// Clean roof values, nulling anything outside the known set.
+fn clean_roof(mut lf: LazyFrame) -> Result<LazyFrame> {
+    let valid_roof = &["dome", "outdoors", "closed", "open"];
+    if !lf.collect_schema()?.contains("roof") {
+        return Ok(lf);
+    }
+    Ok(lf.with_columns([when(
+        col("roof").is_in(lit(Series::new(PlSmallStr::from("roof"), valid_roof))),
+    )
+    .then(col("roof"))
+    .otherwise(lit(NULL))
+    .alias("roof")]))
 }
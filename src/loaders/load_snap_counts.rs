@@ -1,33 +1,33 @@
 use polars::prelude::*;
 
-use crate::downloader::download_nflverse;
+use crate::catalog::SNAP_COUNTS;
+use crate::downloader::{
+    download_nflverse_all, download_nflverse_all_async, scan_nflverse_all, scan_nflverse_all_async,
+};
 use crate::error::Result;
-use crate::loaders::seasons::resolve_seasons;
-
-const FIRST_SEASON: i32 = 2012;
+use crate::loaders::frames::{concat_frames, concat_frames_async, concat_lazy};
+use crate::loaders::seasons::Seasons;
 
 /// Load snap count data for the given seasons.
-pub fn load_snap_counts(seasons: Option<Vec<i32>>) -> Result<DataFrame> {
-    let season_list = resolve_seasons(seasons, FIRST_SEASON)?;
-    let mut dfs = Vec::new();
+pub fn load_snap_counts(seasons: impl Into<Seasons>) -> Result<DataFrame> {
+    let paths = SNAP_COUNTS.resolve_paths(&[], &seasons.into())?;
+    concat_frames(download_nflverse_all(&paths)?)
+}
 
-    for season in &season_list {
-        let path = format!("snap_counts/snap_counts_{season}");
-        let df = download_nflverse(&path)?;
-        dfs.push(df);
-    }
+/// Async version of [`load_snap_counts`].
+pub async fn load_snap_counts_async(seasons: impl Into<Seasons>) -> Result<DataFrame> {
+    let paths = SNAP_COUNTS.resolve_paths(&[], &seasons.into())?;
+    concat_frames_async(download_nflverse_all_async(&paths).await?).await
+}
 
-    if dfs.len() == 1 {
-        return Ok(dfs.into_iter().next().unwrap());
-    }
+/// Lazy version of [`load_snap_counts`]. See [`crate::downloader::scan_parquet`].
+pub fn scan_snap_counts(seasons: impl Into<Seasons>) -> Result<LazyFrame> {
+    let paths = SNAP_COUNTS.resolve_paths(&[], &seasons.into())?;
+    concat_lazy(scan_nflverse_all(&paths)?)
+}
 
-    let lazy_frames: Vec<LazyFrame> = dfs.into_iter().map(|df| df.lazy()).collect();
-    Ok(concat(lazy_frames, UnionArgs {
-        parallel: true,
-        rechunk: true,
-        to_supertypes: true,
-        diagonal: true,
-        from_partitioned_ds: false,
-        maintain_order: true,
-    })?.collect()?)
+/// Async version of [`scan_snap_counts`].
+pub async fn scan_snap_counts_async(seasons: impl Into<Seasons>) -> Result<LazyFrame> {
+    let paths = SNAP_COUNTS.resolve_paths(&[], &seasons.into())?;
+    concat_lazy(scan_nflverse_all_async(&paths).await?)
 }
@@ -1,49 +1,62 @@
 use polars::prelude::*;
 
-use crate::downloader::download_nflverse;
-use crate::error::{NflReadError, Result};
-use crate::loaders::seasons::resolve_seasons;
-
-const FIRST_SEASON: i32 = 1999;
+use crate::catalog::TEAM_STATS;
+use crate::downloader::{
+    download_nflverse_all, download_nflverse_all_async, scan_nflverse_all, scan_nflverse_all_async,
+};
+use crate::error::Result;
+use crate::loaders::frames::{concat_frames, concat_frames_async, concat_lazy};
+use crate::loaders::seasons::Seasons;
+use crate::params::SummaryLevel;
 
 /// Load team stats for the given seasons.
 ///
-/// `summary_level` can be: "week", "reg", "post", or "reg+post"
+/// `summary_level` picks weekly rows or regular season, postseason or
+/// combined totals.
 pub fn load_team_stats(
-    seasons: Option<Vec<i32>>,
-    summary_level: &str,
+    seasons: impl Into<Seasons>,
+    summary_level: SummaryLevel,
 ) -> Result<DataFrame> {
-    validate_summary_level(summary_level)?;
-    let level_str = summary_level.replace('+', "");
-    let season_list = resolve_seasons(seasons, FIRST_SEASON)?;
-    let mut dfs = Vec::new();
-
-    for season in &season_list {
-        let path = format!("stats_team/stats_team_{level_str}_{season}");
-        let df = download_nflverse(&path)?;
-        dfs.push(df);
-    }
+    let paths = TEAM_STATS.resolve_paths(
+        &[("summary_level", summary_level.as_str())],
+        &seasons.into(),
+    )?;
+    concat_frames(download_nflverse_all(&paths)?)
+}
 
-    if dfs.len() == 1 {
-        return Ok(dfs.into_iter().next().unwrap());
-    }
+/// Async version of [`load_team_stats`].
+pub async fn load_team_stats_async(
+    seasons: impl Into<Seasons>,
+    summary_level: SummaryLevel,
+) -> Result<DataFrame> {
+    let paths = TEAM_STATS.resolve_paths(
+        &[("summary_level", summary_level.as_str())],
+        &seasons.into(),
+    )?;
+    concat_frames_async(download_nflverse_all_async(&paths).await?).await
+}
 
-    let lazy_frames: Vec<LazyFrame> = dfs.into_iter().map(|df| df.lazy()).collect();
-    Ok(concat(lazy_frames, UnionArgs {
-        parallel: true,
-        rechunk: true,
-        to_supertypes: true,
-        diagonal: true,
-        from_partitioned_ds: false,
-        maintain_order: true,
-    })?.collect()?)
+/// Lazy version of [`load_team_stats`], scanning each season's file for the
+/// summary level. See [`crate::downloader::scan_parquet`].
+pub fn scan_team_stats(
+    seasons: impl Into<Seasons>,
+    summary_level: SummaryLevel,
+) -> Result<LazyFrame> {
+    let paths = TEAM_STATS.resolve_paths(
+        &[("summary_level", summary_level.as_str())],
+        &seasons.into(),
+    )?;
+    concat_lazy(scan_nflverse_all(&paths)?)
 }
 
-fn validate_summary_level(level: &str) -> Result<()> {
-    match level {
-        "week" | "reg" | "post" | "reg+post" => Ok(()),
-        _ => Err(NflReadError::InvalidParameter(format!(
-            "Invalid summary_level: '{level}'. Must be one of: week, reg, post, reg+post"
-        ))),
-    }
+/// Async version of [`scan_team_stats`].
+pub async fn scan_team_stats_async(
+    seasons: impl Into<Seasons>,
+    summary_level: SummaryLevel,
+) -> Result<LazyFrame> {
+    let paths = TEAM_STATS.resolve_paths(
+        &[("summary_level", summary_level.as_str())],
+        &seasons.into(),
+    )?;
+    concat_lazy(scan_nflverse_all_async(&paths).await?)
 }
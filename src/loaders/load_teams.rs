@@ -1,9 +1,27 @@
-use polars::prelude::DataFrame;
+use polars::prelude::{DataFrame, LazyFrame};
 
-use crate::downloader::download_nflverse;
+use crate::catalog::TEAMS;
+use crate::downloader::{
+    download_nflverse, download_nflverse_async, scan_nflverse, scan_nflverse_async,
+};
 use crate::error::Result;
 
 /// Load team metadata (colors, logos, etc.). No season parameter needed.
 pub fn load_teams() -> Result<DataFrame> {
-    download_nflverse("teams/teams_colors_logos")
+    download_nflverse(&TEAMS.path(&[])?)
+}
+
+/// Async version of [`load_teams`].
+pub async fn load_teams_async() -> Result<DataFrame> {
+    download_nflverse_async(&TEAMS.path(&[])?).await
+}
+
+/// Lazy version of [`load_teams`]. See [`crate::downloader::scan_parquet`].
+pub fn scan_teams() -> Result<LazyFrame> {
+    scan_nflverse(&TEAMS.path(&[])?)
+}
+
+/// Async version of [`scan_teams`].
+pub async fn scan_teams_async() -> Result<LazyFrame> {
+    scan_nflverse_async(&TEAMS.path(&[])?).await
 }
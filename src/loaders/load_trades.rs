@@ -1,9 +1,27 @@
-use polars::prelude::DataFrame;
+use polars::prelude::{DataFrame, LazyFrame};
 
-use crate::downloader::download_nflverse;
+use crate::catalog::TRADES;
+use crate::downloader::{
+    download_nflverse, download_nflverse_async, scan_nflverse, scan_nflverse_async,
+};
 use crate::error::Result;
 
 /// Load trade data. No season parameter needed.
 pub fn load_trades() -> Result<DataFrame> {
-    download_nflverse("trades/trades")
+    download_nflverse(&TRADES.path(&[])?)
+}
+
+/// Async version of [`load_trades`].
+pub async fn load_trades_async() -> Result<DataFrame> {
+    download_nflverse_async(&TRADES.path(&[])?).await
+}
+
+/// Lazy version of [`load_trades`]. See [`crate::downloader::scan_parquet`].
+pub fn scan_trades() -> Result<LazyFrame> {
+    scan_nflverse(&TRADES.path(&[])?)
+}
+
+/// Async version of [`scan_trades`].
+pub async fn scan_trades_async() -> Result<LazyFrame> {
+    scan_nflverse_async(&TRADES.path(&[])?).await
 }
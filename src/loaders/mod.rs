@@ -1,5 +1,7 @@
+pub(crate) mod frames;
 pub mod load_combine;
 pub mod load_contracts;
+pub mod load_dataset;
 pub mod load_depth_charts;
 pub mod load_draft_picks;
 pub mod load_ffverse;
@@ -19,4 +21,4 @@ pub mod load_snap_counts;
 pub mod load_team_stats;
 pub mod load_teams;
 pub mod load_trades;
-pub(crate) mod seasons;
+pub mod seasons;
@@ -1,72 +1,245 @@
+use std::fmt;
+use std::ops::{RangeFrom, RangeInclusive};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
 use crate::error::{NflReadError, Result};
-use crate::utils_date::get_current_season;
-
-/// Represents the `seasons` parameter that most loader functions accept.
-/// - `None` => current season only
-/// - `true` (All) => all available seasons from `first_season` to current
-/// - Single season int
-/// - List of season ints
-#[derive(Debug, Clone)]
-#[allow(dead_code)]
+
+/// The first NFL season, bounding `Last` for datasets without a first season.
+const FIRST_NFL_SEASON: i32 = 1920;
+
+/// The seasons a loader should return.
+///
+/// Every loader that takes seasons accepts anything convertible into
+/// `Seasons`: a year (`2023`), a list (`vec![2022, 2023]`, `[2022, 2023]`),
+/// an inclusive range (`2020..=2023`) or an open range (`2015..`).
+///
+/// The same selection means the same thing for every dataset, whether it is
+/// published as one file per season or as a single file filtered by its
+/// `season` column:
+///
+/// - `Current` (the default) is the current season, by the dataset's cutoff
+///   date (March 15 for rosters, the Thursday after Labor Day otherwise).
+/// - `All` is every season from the dataset's first season to the current one.
+/// - `Since(year)` is every season from `year` to the current one.
+/// - `Last(n)` is the `n` most recent seasons, including the current one,
+///   stopping at the dataset's first season (or at 1920 for datasets without
+///   one). `Last(0)` fails with `NflReadError::InvalidParameter`.
+///
+/// Naming a season before the dataset's first season (explicitly, as the
+/// start of a range, or with `Since`) fails with `NflReadError::InvalidSeason`.
+///
+/// Parsing accepts `current`, `all`, `2023`, `2020-2023`, `2015..`, `last:3`
+/// and comma-separated mixes of years and ranges such as `2019,2021-2023`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "SeasonsRepr", into = "SeasonsRepr")]
 pub enum Seasons {
+    #[default]
     Current,
     All,
     Single(i32),
     Multiple(Vec<i32>),
+    Range(RangeInclusive<i32>),
+    Since(i32),
+    Last(u32),
 }
 
 impl Seasons {
-    /// Resolve to a concrete list of season years.
-    /// `first_season` is the earliest season for which data is available.
-    /// `roster` controls whether the roster cutoff date is used.
-    pub fn resolve(&self, first_season: i32, roster: bool) -> Result<Vec<i32>> {
-        let current = get_current_season(roster);
-        match self {
-            Seasons::Current => Ok(vec![current]),
-            Seasons::All => {
-                if first_season > current {
+    /// Resolve to a concrete list of season years, given the dataset's first
+    /// season (if it has one) and its current season.
+    pub(crate) fn resolve(&self, first_season: Option<i32>, current: i32) -> Result<Vec<i32>> {
+        let seasons: Vec<i32> = match self {
+            Seasons::Current => vec![current],
+            Seasons::All => match first_season {
+                Some(first) => (first..=current).collect(),
+                None => {
+                    return Err(NflReadError::InvalidSeason(
+                        "All seasons requested from a dataset without a first season".into(),
+                    ));
+                }
+            },
+            Seasons::Single(s) => vec![*s],
+            Seasons::Multiple(seasons) => seasons.clone(),
+            Seasons::Range(range) => {
+                if range.is_empty() {
                     return Err(NflReadError::InvalidSeason(format!(
-                        "First available season ({first_season}) is after current season ({current})"
+                        "Empty season range {}-{}",
+                        range.start(),
+                        range.end()
                     )));
                 }
-                Ok((first_season..=current).collect())
+                range.clone().collect()
             }
-            Seasons::Single(s) => {
-                if *s < first_season {
-                    return Err(NflReadError::InvalidSeason(format!(
-                        "Season {s} is before first available season ({first_season})"
-                    )));
+            Seasons::Since(s) => (*s..=current).collect(),
+            Seasons::Last(n) => {
+                if *n == 0 {
+                    return Err(NflReadError::InvalidParameter(
+                        "Last(0) selects no seasons".into(),
+                    ));
                 }
-                Ok(vec![*s])
+                let n = i32::try_from(*n).unwrap_or(i32::MAX);
+                let start = current.saturating_sub(n - 1);
+                (start.max(first_season.unwrap_or(FIRST_NFL_SEASON))..=current).collect()
             }
+        };
+
+        if let (Seasons::Since(s), true) = (self, seasons.is_empty()) {
+            return Err(NflReadError::InvalidSeason(format!(
+                "Season {s} is after the current season ({current})"
+            )));
+        }
+        if let Some(first) = first_season
+            && let Some(s) = seasons.iter().find(|s| **s < first)
+        {
+            return Err(NflReadError::InvalidSeason(format!(
+                "Season {s} is before first available season ({first})"
+            )));
+        }
+        Ok(seasons)
+    }
+}
+
+impl From<i32> for Seasons {
+    fn from(season: i32) -> Self {
+        Seasons::Single(season)
+    }
+}
+
+impl From<Vec<i32>> for Seasons {
+    fn from(seasons: Vec<i32>) -> Self {
+        Seasons::Multiple(seasons)
+    }
+}
+
+impl From<&[i32]> for Seasons {
+    fn from(seasons: &[i32]) -> Self {
+        Seasons::Multiple(seasons.to_vec())
+    }
+}
+
+impl<const N: usize> From<[i32; N]> for Seasons {
+    fn from(seasons: [i32; N]) -> Self {
+        Seasons::Multiple(seasons.to_vec())
+    }
+}
+
+impl From<RangeInclusive<i32>> for Seasons {
+    fn from(range: RangeInclusive<i32>) -> Self {
+        Seasons::Range(range)
+    }
+}
+
+impl From<RangeFrom<i32>> for Seasons {
+    fn from(range: RangeFrom<i32>) -> Self {
+        Seasons::Since(range.start)
+    }
+}
+
+impl fmt::Display for Seasons {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Seasons::Current => f.write_str("current"),
+            Seasons::All => f.write_str("all"),
+            Seasons::Single(s) => write!(f, "{s}"),
             Seasons::Multiple(seasons) => {
-                for s in seasons {
-                    if *s < first_season {
-                        return Err(NflReadError::InvalidSeason(format!(
-                            "Season {s} is before first available season ({first_season})"
-                        )));
-                    }
-                }
-                Ok(seasons.clone())
+                let seasons: Vec<String> = seasons.iter().map(i32::to_string).collect();
+                f.write_str(&seasons.join(","))
+            }
+            Seasons::Range(range) => write!(f, "{}-{}", range.start(), range.end()),
+            Seasons::Since(s) => write!(f, "{s}.."),
+            Seasons::Last(n) => write!(f, "last:{n}"),
+        }
+    }
+}
+
+impl FromStr for Seasons {
+    type Err = NflReadError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = |detail: &str| {
+            NflReadError::InvalidSeason(format!(
+                "Invalid seasons '{s}': {detail}. Expected e.g. 2023, 2020-2023, \
+                 2019,2021-2023, 2015.., last:3, all or current"
+            ))
+        };
+        let year = |v: &str| {
+            v.trim()
+                .parse::<i32>()
+                .map_err(|_| invalid(&format!("'{}' is not a season", v.trim())))
+        };
+        let range = |part: &str, start: &str, end: &str| {
+            let (start, end) = (year(start)?, year(end)?);
+            if start > end {
+                return Err(invalid(&format!("range '{part}' is reversed")));
+            }
+            Ok(start..=end)
+        };
+
+        let s = s.trim();
+        match s.to_lowercase().as_str() {
+            "current" => return Ok(Seasons::Current),
+            "all" => return Ok(Seasons::All),
+            _ => {}
+        }
+        if let Some(n) = s.strip_prefix("last:") {
+            return match n.trim().parse::<u32>() {
+                Ok(0) => Err(NflReadError::InvalidParameter(
+                    "'last:0' selects no seasons".into(),
+                )),
+                Ok(n) => Ok(Seasons::Last(n)),
+                Err(_) => Err(invalid("expected a positive count after 'last:'")),
+            };
+        }
+        if let Some(start) = s.strip_suffix("..") {
+            return Ok(Seasons::Since(year(start)?));
+        }
+        if !s.contains(',') {
+            return match s.split_once('-') {
+                Some((start, end)) => Ok(Seasons::Range(range(s, start, end)?)),
+                None => Ok(Seasons::Single(year(s)?)),
+            };
+        }
+
+        let mut seasons = Vec::new();
+        for part in s.split(',').map(str::trim) {
+            match part.split_once('-') {
+                Some((start, end)) => seasons.extend(range(part, start, end)?),
+                None => seasons.push(year(part)?),
             }
         }
+        Ok(Seasons::Multiple(seasons))
     }
 }
 
-/// Helper to resolve seasons and pass `roster=false` (default for most loaders).
-pub fn resolve_seasons(seasons: Option<Vec<i32>>, first_season: i32) -> Result<Vec<i32>> {
-    let s = match seasons {
-        None => Seasons::Current,
-        Some(v) => Seasons::Multiple(v),
-    };
-    s.resolve(first_season, false)
+/// How `Seasons` appear in configuration files: a year, a list of years, or
+/// any other selection as a string such as `"2015.."`.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum SeasonsRepr {
+    Single(i32),
+    Multiple(Vec<i32>),
+    Text(String),
+}
+
+impl TryFrom<SeasonsRepr> for Seasons {
+    type Error = NflReadError;
+
+    fn try_from(repr: SeasonsRepr) -> Result<Self> {
+        match repr {
+            SeasonsRepr::Single(s) => Ok(Seasons::Single(s)),
+            SeasonsRepr::Multiple(seasons) => Ok(Seasons::Multiple(seasons)),
+            SeasonsRepr::Text(text) => text.parse(),
+        }
+    }
 }
 
-/// Helper to resolve seasons with `roster=true` (for roster-related loaders).
-pub fn resolve_seasons_roster(seasons: Option<Vec<i32>>, first_season: i32) -> Result<Vec<i32>> {
-    let s = match seasons {
-        None => Seasons::Current,
-        Some(v) => Seasons::Multiple(v),
-    };
-    s.resolve(first_season, true)
+impl From<Seasons> for SeasonsRepr {
+    fn from(seasons: Seasons) -> Self {
+        match seasons {
+            Seasons::Single(s) => SeasonsRepr::Single(s),
+            Seasons::Multiple(seasons) => SeasonsRepr::Multiple(seasons),
+            other => SeasonsRepr::Text(other.to_string()),
+        }
+    }
 }
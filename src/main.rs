@@ -1,15 +1,563 @@
+use std::path::{Path, PathBuf};
+
+use clap::{Args, Parser, Subcommand};
+use nflreadrust::config::{get_config, update_config};
 use nflreadrust::*;
+use polars::prelude::*;
+
+/// Read NFL data from the nflverse project.
+#[derive(Parser)]
+#[command(name = "nflreadrust", version)]
+struct Cli {
+    /// Cache mode: memory, filesystem or off (overrides NFLREADRUST_CACHE).
+    /// Defaults to filesystem, as a memory cache does not outlive the process.
+    #[arg(long, global = true, value_parser = parse_cache_mode)]
+    cache: Option<CacheMode>,
+    /// Serve only from the cache, never touching the network.
+    #[arg(long, global = true)]
+    offline: bool,
+    /// Print download URLs.
+    #[arg(long, short, global = true)]
+    verbose: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Play-by-play data (from 1999).
+    Pbp(SeasonArgs),
+    /// Play participation data (from 2016).
+    Participation(SeasonArgs),
+    /// Game schedules and results.
+    Schedules(SeasonArgs),
+    /// Player stats.
+    PlayerStats {
+        /// Summary level: week, reg, post or reg+post.
+        #[arg(long)]
+        level: Option<String>,
+        #[command(flatten)]
+        args: SeasonArgs,
+    },
+    /// Team stats.
+    TeamStats {
+        /// Summary level: week, reg, post or reg+post.
+        #[arg(long)]
+        level: Option<String>,
+        #[command(flatten)]
+        args: SeasonArgs,
+    },
+    /// Next Gen Stats.
+    Nextgen {
+        /// Stat type: passing, receiving or rushing.
+        #[arg(long = "type")]
+        stat_type: Option<String>,
+        #[command(flatten)]
+        args: SeasonArgs,
+    },
+    /// Pro Football Reference advanced stats.
+    PfrAdvstats {
+        /// Stat type: pass, rush, rec or def.
+        #[arg(long = "type")]
+        stat_type: Option<String>,
+        /// Summary level: week or season.
+        #[arg(long)]
+        level: Option<String>,
+        #[command(flatten)]
+        args: SeasonArgs,
+    },
+    /// Snap counts (from 2012).
+    SnapCounts(SeasonArgs),
+    /// Season rosters (from 1920).
+    Rosters(SeasonArgs),
+    /// Weekly rosters (from 2002).
+    RostersWeekly(SeasonArgs),
+    /// Player biographical information.
+    Players(OutputArgs),
+    /// Depth charts (from 2001).
+    DepthCharts(SeasonArgs),
+    /// Injury reports (from 2009).
+    Injuries(SeasonArgs),
+    /// Game officials.
+    Officials(SeasonArgs),
+    /// Draft picks.
+    DraftPicks(SeasonArgs),
+    /// NFL combine results.
+    Combine(SeasonArgs),
+    /// Historical contract data.
+    Contracts(OutputArgs),
+    /// Trade data.
+    Trades(OutputArgs),
+    /// Team metadata, colors and logos.
+    Teams(OutputArgs),
+    /// FTN charting data (from 2022).
+    FtnCharting(SeasonArgs),
+    /// Fantasy player ID mappings.
+    FfPlayerids(OutputArgs),
+    /// Fantasy rankings.
+    FfRankings {
+        /// Ranking type: draft, week or all.
+        #[arg(long = "type")]
+        ranking_type: Option<String>,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Fantasy opportunity data (from 2006).
+    FfOpportunity {
+        /// Stat type: weekly, pbp_pass or pbp_rush.
+        #[arg(long = "type")]
+        stat_type: Option<String>,
+        /// Model version: latest or v1.0.0.
+        #[arg(long)]
+        model_version: Option<String>,
+        #[command(flatten)]
+        args: SeasonArgs,
+    },
+    /// Mirror every known dataset into a directory.
+    Snapshot { dir: PathBuf },
+    /// Inspect and manage the filesystem cache.
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheCommand {
+    /// List cached entries with their dataset, size, age and expiry.
+    List {
+        /// Only list entries for this dataset.
+        #[arg(long, short)]
+        dataset: Option<String>,
+    },
+    /// Summarize the cache directory.
+    Info,
+    /// Remove cached entries for a dataset, or everything when none is given.
+    Clear { dataset: Option<String> },
+    /// Remove expired entries.
+    Prune,
+}
+
+#[derive(Args)]
+struct SeasonArgs {
+    /// Seasons to load, e.g. `2023`, `2020-2023`, `2019,2021-2023`, `2015..`,
+    /// `last:3` or `all`. Defaults to the current season.
+    #[arg(long, short, value_parser = parse_seasons)]
+    seasons: Option<Seasons>,
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+#[derive(Args)]
+struct OutputArgs {
+    /// Columns to keep, comma-separated.
+    #[arg(long, short, value_delimiter = ',')]
+    columns: Vec<String>,
+    /// Row filter such as `team=KC`, `week>=10` or `position!=QB`.
+    /// Repeat to combine filters.
+    #[arg(long = "filter", short, value_parser = parse_filter)]
+    filters: Vec<RowFilter>,
+    /// Keep at most this many rows.
+    #[arg(long)]
+    limit: Option<u32>,
+    /// Write to this file instead of stdout.
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+    /// Output format: csv, parquet, json, ndjson or ipc. Inferred from the
+    /// output file extension, and csv when writing to stdout.
+    #[arg(long)]
+    format: Option<ExportFormat>,
+    /// Parquet compression: uncompressed, snappy, gzip, lz4, zstd or brotli.
+    #[arg(long, default_value_t = Compression::default())]
+    compression: Compression,
+}
+
+#[derive(Clone, Copy)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Clone)]
+struct RowFilter {
+    column: String,
+    op: Op,
+    value: String,
+}
+
+/// Prints the library's warnings to stderr.
+struct StderrLogger;
+
+impl log::Log for StderrLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Warn
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("Warning: {}", record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
 
 fn main() {
-    println!("nflreadrust - NFL data loader");
-    println!("Current NFL season: {}", get_current_season(false));
+    let cli = Cli::parse();
+    if log::set_logger(&StderrLogger).is_ok() {
+        log::set_max_level(log::LevelFilter::Warn);
+    }
+
+    let mut config = get_config();
+    if let Some(mode) = cli.cache {
+        config.cache_mode = mode;
+    } else if std::env::var_os("NFLREADRUST_CACHE").is_none() {
+        config.cache_mode = CacheMode::Filesystem;
+    }
+    config.offline |= cli.offline;
+    config.verbose |= cli.verbose;
+    update_config(config);
 
-    // Example: load teams data
-    match load_teams() {
-        Ok(df) => {
-            println!("\nTeams data:");
-            println!("{}", df.head(Some(5)));
+    let result = match cli.command {
+        Command::Snapshot { dir } => run_snapshot(&dir),
+        Command::Cache { command } => run_cache(command),
+        command => run_load(command.into()),
+    };
+    if let Err(e) = result {
+        eprintln!("Error: {e}");
+        std::process::exit(1);
+    }
+}
+
+/// A catalog dataset with its load parameters and output options.
+struct Request {
+    dataset: &'static str,
+    params: LoadParams,
+    output: OutputArgs,
+}
+
+impl Request {
+    fn new(dataset: &'static str, output: OutputArgs) -> Self {
+        Request {
+            dataset,
+            params: LoadParams::default(),
+            output,
         }
-        Err(e) => eprintln!("Error loading teams: {e}"),
+    }
+
+    fn seasons(dataset: &'static str, args: SeasonArgs) -> Self {
+        let mut request = Request::new(dataset, args.output);
+        request.params.seasons = args.seasons;
+        request
+    }
+
+    /// Set a dataset parameter if it was given on the command line.
+    fn param(mut self, name: &str, value: Option<String>) -> Self {
+        if let Some(value) = value {
+            self.params.params.insert(name.to_string(), value);
+        }
+        self
+    }
+}
+
+impl From<Command> for Request {
+    fn from(command: Command) -> Self {
+        match command {
+            Command::Pbp(args) => Request::seasons("pbp", args),
+            Command::Participation(args) => Request::seasons("participation", args),
+            Command::Schedules(args) => Request::seasons("schedules", args),
+            Command::PlayerStats { level, args } => {
+                Request::seasons("player_stats", args).param("summary_level", level)
+            }
+            Command::TeamStats { level, args } => {
+                Request::seasons("team_stats", args).param("summary_level", level)
+            }
+            Command::Nextgen { stat_type, args } => {
+                Request::seasons("nextgen_stats", args).param("stat_type", stat_type)
+            }
+            Command::PfrAdvstats {
+                stat_type,
+                level,
+                args,
+            } => Request::seasons("pfr_advstats", args)
+                .param("stat_type", stat_type)
+                .param("summary_level", level),
+            Command::SnapCounts(args) => Request::seasons("snap_counts", args),
+            Command::Rosters(args) => Request::seasons("rosters", args),
+            Command::RostersWeekly(args) => Request::seasons("rosters_weekly", args),
+            Command::Players(output) => Request::new("players", output),
+            Command::DepthCharts(args) => Request::seasons("depth_charts", args),
+            Command::Injuries(args) => Request::seasons("injuries", args),
+            Command::Officials(args) => Request::seasons("officials", args),
+            Command::DraftPicks(args) => Request::seasons("draft_picks", args),
+            Command::Combine(args) => Request::seasons("combine", args),
+            Command::Contracts(output) => Request::new("contracts", output),
+            Command::Trades(output) => Request::new("trades", output),
+            Command::Teams(output) => Request::new("teams", output),
+            Command::FtnCharting(args) => Request::seasons("ftn_charting", args),
+            Command::FfPlayerids(output) => Request::new("ff_playerids", output),
+            Command::FfRankings {
+                ranking_type,
+                output,
+            } => Request::new("ff_rankings", output).param("ranking_type", ranking_type),
+            Command::FfOpportunity {
+                stat_type,
+                model_version,
+                args,
+            } => Request::seasons("ff_opportunity", args)
+                .param("stat_type", stat_type)
+                .param("model_version", model_version),
+            Command::Snapshot { .. } | Command::Cache { .. } => {
+                unreachable!("not a dataset command")
+            }
+        }
+    }
+}
+
+/// Load the dataset named by `request` and write it out.
+fn run_load(mut request: Request) -> Result<(), NflReadError> {
+    request.params.columns = loaded_columns(&request.output);
+    let df = load(request.dataset, &request.params)?;
+    let mut df = apply_output_args(df, &request.output)?;
+
+    let output = &request.output;
+    match &output.output {
+        Some(path) => export_dataframe(&mut df, path, output.format, output.compression),
+        None => {
+            let format = output.format.unwrap_or(ExportFormat::Csv);
+            write_dataframe(
+                &mut df,
+                std::io::stdout().lock(),
+                format,
+                output.compression,
+            )
+        }
+    }
+}
+
+/// The columns to load: those selected plus any that filters refer to, so
+/// datasets that can project while reading never hold the rest. `None`, for
+/// every column, when no selection was given.
+fn loaded_columns(output: &OutputArgs) -> Option<Vec<String>> {
+    if output.columns.is_empty() {
+        return None;
+    }
+    let mut columns = output.columns.clone();
+    for filter in &output.filters {
+        if !columns.contains(&filter.column) {
+            columns.push(filter.column.clone());
+        }
+    }
+    Some(columns)
+}
+
+/// Apply row filters, column selection and the row limit.
+fn apply_output_args(df: DataFrame, output: &OutputArgs) -> Result<DataFrame, NflReadError> {
+    let schema = df.schema().clone();
+    let mut lf = df.lazy();
+    for filter in &output.filters {
+        let Some(dtype) = schema.get(filter.column.as_str()) else {
+            return Err(NflReadError::InvalidParameter(format!(
+                "Unknown column in filter: '{}'",
+                filter.column
+            )));
+        };
+        let value = lit(filter.value.clone()).strict_cast(dtype.clone());
+        let column = col(filter.column.as_str());
+        lf = lf.filter(match filter.op {
+            Op::Eq => column.eq(value),
+            Op::Ne => column.neq(value),
+            Op::Lt => column.lt(value),
+            Op::Le => column.lt_eq(value),
+            Op::Gt => column.gt(value),
+            Op::Ge => column.gt_eq(value),
+        });
+    }
+    if !output.columns.is_empty() {
+        if let Some(missing) = output.columns.iter().find(|c| !schema.contains(c.as_str())) {
+            return Err(NflReadError::InvalidParameter(format!(
+                "Unknown column: '{missing}'"
+            )));
+        }
+        lf = lf.select(
+            output
+                .columns
+                .iter()
+                .map(|c| col(c.as_str()))
+                .collect::<Vec<_>>(),
+        );
+    }
+    if let Some(limit) = output.limit {
+        lf = lf.limit(limit);
+    }
+    Ok(lf.collect()?)
+}
+
+/// Parse a cache mode strictly, unlike `CacheMode::from_str`, so a typo is
+/// an error rather than `memory`.
+fn parse_cache_mode(s: &str) -> Result<CacheMode, String> {
+    match s.to_lowercase().as_str() {
+        "memory" => Ok(CacheMode::Memory),
+        "filesystem" => Ok(CacheMode::Filesystem),
+        "off" => Ok(CacheMode::Off),
+        _ => Err("must be one of: memory, filesystem, off".to_string()),
+    }
+}
+
+/// Parse a [`Seasons`] selection such as `2023`, `2020-2023` or `2015..`.
+fn parse_seasons(s: &str) -> Result<Seasons, String> {
+    s.parse().map_err(|e: NflReadError| e.to_string())
+}
+
+/// Parse `COLUMN<op>VALUE` where op is one of `=`, `!=`, `<`, `<=`, `>`, `>=`.
+fn parse_filter(s: &str) -> Result<RowFilter, String> {
+    let invalid = || format!("expected COLUMN<op>VALUE (e.g. team=KC), got '{s}'");
+    let pos = s.find(['=', '!', '<', '>']).ok_or_else(invalid)?;
+    let (column, rest) = s.split_at(pos);
+    let operators = [
+        ("!=", Op::Ne),
+        ("<=", Op::Le),
+        (">=", Op::Ge),
+        ("==", Op::Eq),
+        ("=", Op::Eq),
+        ("<", Op::Lt),
+        (">", Op::Gt),
+    ];
+    let (op, value) = operators
+        .into_iter()
+        .find_map(|(symbol, op)| rest.strip_prefix(symbol).map(|value| (op, value)))
+        .ok_or_else(invalid)?;
+    if column.trim().is_empty() {
+        return Err(invalid());
+    }
+    Ok(RowFilter {
+        column: column.trim().to_string(),
+        op,
+        value: value.trim().to_string(),
+    })
+}
+
+/// `nflreadrust snapshot <dir>`: mirror every known dataset into `dir`.
+fn run_snapshot(dir: &Path) -> Result<(), NflReadError> {
+    let manifest = snapshot(dir)?;
+    let bytes: u64 = manifest.files.iter().map(|f| f.size).sum();
+    println!(
+        "Wrote {} files ({bytes} bytes) to {}",
+        manifest.files.len(),
+        dir.display()
+    );
+    if !manifest.failures.is_empty() {
+        println!("{} files could not be downloaded:", manifest.failures.len());
+        for failure in &manifest.failures {
+            println!("  {}: {}", failure.url, failure.error);
+        }
+    }
+    Ok(())
+}
+
+/// `nflreadrust cache ...`: manage the on-disk cache.
+fn run_cache(command: CacheCommand) -> Result<(), NflReadError> {
+    // These commands act on the cache directory whatever the configured mode.
+    let mut config = get_config();
+    config.cache_mode = CacheMode::Filesystem;
+    update_config(config.clone());
+
+    match command {
+        CacheCommand::List { dataset } => {
+            let dataset = dataset.as_deref().map(lookup_dataset).transpose()?;
+            println!(
+                "{:<16} {:>10} {:>8} {:>9}  URL",
+                "DATASET", "SIZE", "AGE", "EXPIRES"
+            );
+            for entry in cache_entries() {
+                if dataset.is_some_and(|d| entry.dataset != Some(d.name)) {
+                    continue;
+                }
+                let expires = if entry.is_expired() {
+                    "expired".to_string()
+                } else {
+                    format!("in {}", format_duration(entry.expires_in_secs))
+                };
+                println!(
+                    "{:<16} {:>10} {:>8} {:>9}  {}",
+                    entry.dataset.unwrap_or("-"),
+                    format_size(entry.size),
+                    format_duration(entry.age_secs),
+                    expires,
+                    entry.url.as_deref().unwrap_or(&entry.key)
+                );
+            }
+        }
+        CacheCommand::Info => {
+            let entries = cache_entries();
+            let total: u64 = entries.iter().map(|e| e.size).sum();
+            let expired = entries.iter().filter(|e| e.is_expired()).count();
+            println!("Directory: {}", config.cache_dir.display());
+            println!("Duration:  {}", format_duration(config.cache_duration));
+            println!("Entries:   {} ({expired} expired)", entries.len());
+            println!("Size:      {}", format_size(total));
+            if config.disk_cache_max_bytes > 0 {
+                println!("Quota:     {}", format_size(config.disk_cache_max_bytes));
+            }
+
+            let mut by_dataset: Vec<(&str, usize, u64)> = Vec::new();
+            for entry in &entries {
+                let name = entry.dataset.unwrap_or("-");
+                match by_dataset.iter_mut().find(|(n, _, _)| *n == name) {
+                    Some((_, count, size)) => {
+                        *count += 1;
+                        *size += entry.size;
+                    }
+                    None => by_dataset.push((name, 1, entry.size)),
+                }
+            }
+            by_dataset.sort();
+            for (name, count, size) in by_dataset {
+                println!("  {name:<16} {count:>5} entries {:>10}", format_size(size));
+            }
+        }
+        CacheCommand::Clear {
+            dataset: Some(name),
+        } => {
+            let removed = clear_dataset_cache(&name)?;
+            println!("Removed {removed} cached entries for {name}");
+        }
+        CacheCommand::Clear { dataset: None } => {
+            clear_cache(None);
+            println!("Cleared {}", config.cache_dir.display());
+        }
+        CacheCommand::Prune => {
+            println!("Removed {} expired entries", prune_cache());
+        }
+    }
+    Ok(())
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+/// Format seconds as the largest whole unit, e.g. `90s`, `5m`, `3h`, `2d`.
+fn format_duration(secs: u64) -> String {
+    match secs {
+        0..120 => format!("{secs}s"),
+        120..7_200 => format!("{}m", secs / 60),
+        7_200..172_800 => format!("{}h", secs / 3_600),
+        _ => format!("{}d", secs / 86_400),
     }
 }
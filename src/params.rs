@@ -0,0 +1,139 @@
+//! Typed values for the loader parameters that select between release files.
+//!
+//! Each enum parses from and displays as the string used in the catalog (see
+//! [`crate::catalog`]), so command-line and configuration values can still be
+//! given as text: `"reg+post".parse::<SummaryLevel>()`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::NflReadError;
+
+/// Define a parameter enum with its catalog strings, `ALL`, `as_str`,
+/// `Display` and `FromStr`. The first variant is the default.
+macro_rules! parameter_enum {
+    (
+        $(#[$meta:meta])*
+        $name:ident($param:literal) {
+            $(#[$default_meta:meta])*
+            $default:ident => $default_value:literal,
+            $($(#[$variant_meta:meta])* $variant:ident => $value:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        pub enum $name {
+            $(#[$default_meta])*
+            #[default]
+            $default,
+            $($(#[$variant_meta])* $variant,)*
+        }
+
+        impl $name {
+            /// Every value, in catalog order.
+            pub const ALL: &'static [$name] = &[$name::$default, $($name::$variant,)*];
+
+            /// The value as written in the catalog and release paths.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $name::$default => $default_value,
+                    $($name::$variant => $value,)*
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = NflReadError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $name::ALL.iter().copied().find(|v| v.as_str() == s).ok_or_else(|| {
+                    let values: Vec<&str> = $name::ALL.iter().map($name::as_str).collect();
+                    NflReadError::InvalidParameter(format!(
+                        "Invalid {}: '{s}'. Must be one of: {}",
+                        $param,
+                        values.join(", ")
+                    ))
+                })
+            }
+        }
+    };
+}
+
+parameter_enum! {
+    /// Aggregation of player and team stats.
+    SummaryLevel("summary_level") {
+        /// One row per week.
+        Week => "week",
+        /// Regular season totals.
+        Reg => "reg",
+        /// Postseason totals.
+        Post => "post",
+        /// Regular season and postseason combined.
+        RegPost => "reg+post",
+    }
+}
+
+parameter_enum! {
+    /// Next Gen Stats table.
+    NgsStatType("stat_type") {
+        Passing => "passing",
+        Receiving => "receiving",
+        Rushing => "rushing",
+    }
+}
+
+parameter_enum! {
+    /// Pro Football Reference advanced stats table.
+    PfrStatType("stat_type") {
+        Pass => "pass",
+        Rush => "rush",
+        Rec => "rec",
+        Def => "def",
+    }
+}
+
+parameter_enum! {
+    /// Aggregation of Pro Football Reference advanced stats.
+    PfrSummaryLevel("summary_level") {
+        /// One file per season, one row per week.
+        Week => "week",
+        /// A single file of season totals.
+        Season => "season",
+    }
+}
+
+parameter_enum! {
+    /// Fantasy football rankings from dynastyprocess.
+    RankingType("ranking_type") {
+        /// Current draft rankings.
+        Draft => "draft",
+        /// Current weekly rankings.
+        Week => "week",
+        /// Every archived ranking.
+        All => "all",
+    }
+}
+
+parameter_enum! {
+    /// Fantasy football opportunity table.
+    OpportunityStatType("stat_type") {
+        Weekly => "weekly",
+        PbpPass => "pbp_pass",
+        PbpRush => "pbp_rush",
+    }
+}
+
+parameter_enum! {
+    /// Release of the ffopportunity expected points model.
+    ModelVersion("model_version") {
+        Latest => "latest",
+        /// `v1.0.0`.
+        V1 => "v1.0.0",
+    }
+}
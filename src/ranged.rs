@@ -0,0 +1,702 @@
+//! Reading part of a remote Parquet file with HTTP Range requests.
+//!
+//! The footer is fetched first, then only the column chunks of the row groups
+//! whose `season` statistics overlap the requested seasons, limited to the
+//! requested columns when there are any. Those bytes are packed one after
+//! another into a new, smaller file, whose footer is rewritten to list just
+//! the fetched row groups and columns at their new offsets, and the result is
+//! read like any other Parquet file.
+//!
+//! Any response that does not fit this picture (a missing or mismatched
+//! `Content-Range`, a malformed footer, undecodable pages) fails with an
+//! `InvalidData` I/O error, and the caller downloads the whole file instead.
+
+use std::io::Cursor;
+use std::ops::Range;
+
+use bytes::Bytes;
+use polars::prelude::*;
+use polars_parquet_format::thrift::protocol::{TCompactInputProtocol, TCompactOutputProtocol};
+use polars_parquet_format::{
+    ColumnChunk, ColumnMetaData, FileMetaData, KeyValue, RowGroup, SchemaElement, Type,
+};
+use reqwest::StatusCode;
+use reqwest::header::{
+    CONTENT_RANGE, ETAG, HeaderMap, HeaderValue, IF_RANGE, LAST_MODIFIED, RANGE,
+};
+
+use crate::cache::CacheMetadata;
+use crate::config::Config;
+use crate::downloader::{
+    blocking, from_mirrors, from_mirrors_async, get_with_retry, get_with_retry_async,
+    response_metadata,
+};
+use crate::error::{NflReadError, Result};
+
+const MAGIC: &[u8] = b"PAR1";
+/// The key-value metadata entry in which Arrow writers store their schema.
+const ARROW_SCHEMA_KEY: &str = "ARROW:schema";
+/// Bytes requested from the end of the file, enough for most footers.
+const FOOTER_READ_SIZE: u64 = 64 * 1024;
+/// Neighbouring chunks closer than this are fetched with a single request.
+const MAX_RANGE_GAP: u64 = 64 * 1024;
+
+/// The outcome of a ranged read.
+pub(crate) enum RangedRead {
+    /// The selected row groups of the file.
+    Partial(DataFrame),
+    /// The server ignored the `Range` header and sent the whole file.
+    Full(Bytes, CacheMetadata),
+}
+
+/// The answer to a single range request.
+enum Fetched {
+    Partial(Part),
+    Full(Bytes, CacheMetadata),
+}
+
+/// A byte range of the remote file.
+struct Part {
+    bytes: Bytes,
+    start: u64,
+    file_len: u64,
+    /// The `If-Range` value that pins later requests to the same file version.
+    validator: Option<HeaderValue>,
+    metadata: CacheMetadata,
+}
+
+/// Read the row groups of the Parquet file at `url` that may hold rows for
+/// `seasons`, and of those only `columns` (plus `season`) when given.
+///
+/// Every request goes through the retry policy, and the footer request falls
+/// back to the repository's mirrors. Later requests stay on the source that
+/// answered it, since its offsets and `If-Range` validator only hold there.
+pub(crate) fn read_row_groups(
+    url: &str,
+    seasons: &[i32],
+    columns: Option<&[&str]>,
+    config: &Config,
+) -> Result<RangedRead> {
+    let fetch = |url: &str, range: &str, if_range: Option<&HeaderValue>| -> Result<Fetched> {
+        let (status, headers, bytes) = get_with_retry(url, config, range_headers(range, if_range))?;
+        interpret(url, status, &headers, bytes)
+    };
+
+    let tail_range = format!("bytes=-{FOOTER_READ_SIZE}");
+    let (url, tail) = from_mirrors(url, config, |candidate| fetch(candidate, &tail_range, None))?;
+    let tail = match tail {
+        Fetched::Partial(part) => part,
+        Fetched::Full(bytes, metadata) => return Ok(RangedRead::Full(bytes, metadata)),
+    };
+    // A small file fits in the tail request entirely.
+    if tail.start == 0 {
+        return Ok(RangedRead::Full(tail.bytes, tail.metadata));
+    }
+    let if_range = tail.validator.as_ref();
+    let footer = match footer_range(&tail)? {
+        Ok(footer) => footer,
+        Err(range) => match fetch(&url, &range_spec(&range), if_range)? {
+            Fetched::Partial(part) => expect_range(part, &range)?,
+            Fetched::Full(bytes, metadata) => return Ok(RangedRead::Full(bytes, metadata)),
+        },
+    };
+
+    let data_len = tail.file_len - 8 - footer.len() as u64;
+    let plan = Plan::new(decode_footer(&footer)?, data_len, seasons, columns)?;
+    let mut parts = Vec::with_capacity(plan.ranges.len());
+    for range in &plan.ranges {
+        match fetch(&url, &range_spec(range), if_range)? {
+            Fetched::Partial(part) => parts.push((range.start, expect_range(part, range)?)),
+            Fetched::Full(bytes, metadata) => return Ok(RangedRead::Full(bytes, metadata)),
+        }
+    }
+    plan.read(&parts).map(RangedRead::Partial)
+}
+
+/// Async version of [`read_row_groups`].
+pub(crate) async fn read_row_groups_async(
+    url: &str,
+    seasons: &[i32],
+    columns: Option<&[&str]>,
+    config: &Config,
+) -> Result<RangedRead> {
+    let fetch = async |url: &str, range: &str, if_range: Option<&HeaderValue>| -> Result<Fetched> {
+        let (status, headers, bytes) =
+            get_with_retry_async(url, config, range_headers(range, if_range)).await?;
+        interpret(url, status, &headers, bytes)
+    };
+
+    let tail_range = format!("bytes=-{FOOTER_READ_SIZE}");
+    let (url, tail) = from_mirrors_async(url, config, async |candidate| {
+        fetch(candidate, &tail_range, None).await
+    })
+    .await?;
+    let tail = match tail {
+        Fetched::Partial(part) => part,
+        Fetched::Full(bytes, metadata) => return Ok(RangedRead::Full(bytes, metadata)),
+    };
+    // A small file fits in the tail request entirely.
+    if tail.start == 0 {
+        return Ok(RangedRead::Full(tail.bytes, tail.metadata));
+    }
+    let if_range = tail.validator.as_ref();
+    let footer = match footer_range(&tail)? {
+        Ok(footer) => footer,
+        Err(range) => match fetch(&url, &range_spec(&range), if_range).await? {
+            Fetched::Partial(part) => expect_range(part, &range)?,
+            Fetched::Full(bytes, metadata) => return Ok(RangedRead::Full(bytes, metadata)),
+        },
+    };
+
+    let data_len = tail.file_len - 8 - footer.len() as u64;
+    let plan = Plan::new(decode_footer(&footer)?, data_len, seasons, columns)?;
+    let mut parts = Vec::with_capacity(plan.ranges.len());
+    for range in &plan.ranges {
+        match fetch(&url, &range_spec(range), if_range).await? {
+            Fetched::Partial(part) => parts.push((range.start, expect_range(part, range)?)),
+            Fetched::Full(bytes, metadata) => return Ok(RangedRead::Full(bytes, metadata)),
+        }
+    }
+    blocking(move || plan.read(&parts))
+        .await
+        .map(RangedRead::Partial)
+}
+
+fn range_headers(range: &str, if_range: Option<&HeaderValue>) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if let Ok(value) = HeaderValue::from_str(range) {
+        headers.insert(RANGE, value);
+    }
+    if let Some(value) = if_range {
+        headers.insert(IF_RANGE, value.clone());
+    }
+    headers
+}
+
+fn range_spec(range: &Range<u64>) -> String {
+    format!("bytes={}-{}", range.start, range.end - 1)
+}
+
+/// Classify a successful response as a byte range or the whole file.
+fn interpret(url: &str, status: StatusCode, headers: &HeaderMap, bytes: Bytes) -> Result<Fetched> {
+    if status != StatusCode::PARTIAL_CONTENT {
+        return Ok(Fetched::Full(bytes, response_metadata(url, headers)));
+    }
+    let Some((start, file_len)) = headers
+        .get(CONTENT_RANGE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(parse_content_range)
+    else {
+        return Err(invalid("missing or unsupported Content-Range header"));
+    };
+    // If-Range needs a strong validator, so a weak ETag falls back to the date.
+    let validator = headers
+        .get(ETAG)
+        .filter(|etag| !etag.as_bytes().starts_with(b"W/"))
+        .or_else(|| headers.get(LAST_MODIFIED))
+        .cloned();
+    Ok(Fetched::Partial(Part {
+        bytes,
+        start,
+        file_len,
+        validator,
+        metadata: response_metadata(url, headers),
+    }))
+}
+
+/// The start offset and total length from a `Content-Range: bytes a-b/len` header.
+fn parse_content_range(value: &str) -> Option<(u64, u64)> {
+    let (range, len) = value.strip_prefix("bytes ")?.split_once('/')?;
+    let (start, _end) = range.split_once('-')?;
+    Some((start.trim().parse().ok()?, len.trim().parse().ok()?))
+}
+
+/// The bytes of `part`, checked to cover exactly `range`.
+fn expect_range(part: Part, range: &Range<u64>) -> Result<Bytes> {
+    if part.start != range.start || part.bytes.len() as u64 != range.end - range.start {
+        return Err(invalid(
+            "server returned a different byte range than requested",
+        ));
+    }
+    Ok(part.bytes)
+}
+
+/// The encoded footer metadata if `tail` holds all of it, or else the range
+/// of the file to fetch for it.
+fn footer_range(tail: &Part) -> Result<std::result::Result<Bytes, Range<u64>>> {
+    let bytes = &tail.bytes;
+    if tail.start + bytes.len() as u64 != tail.file_len
+        || bytes.len() < 8
+        || &bytes[bytes.len() - 4..] != MAGIC
+    {
+        return Err(invalid("not a Parquet file"));
+    }
+    let len_bytes: [u8; 4] = bytes[bytes.len() - 8..bytes.len() - 4].try_into().unwrap();
+    let footer_len = u32::from_le_bytes(len_bytes) as u64;
+    if footer_len + 8 + MAGIC.len() as u64 > tail.file_len {
+        return Err(invalid("footer length exceeds the file size"));
+    }
+    let footer_end = bytes.len() - 8;
+    match footer_end.checked_sub(footer_len as usize) {
+        Some(footer_start) => Ok(Ok(bytes.slice(footer_start..footer_end))),
+        None => {
+            let end = tail.file_len - 8;
+            Ok(Err(end - footer_len..end))
+        }
+    }
+}
+
+fn decode_footer(bytes: &[u8]) -> Result<FileMetaData> {
+    let mut protocol = TCompactInputProtocol::new(bytes, usize::MAX);
+    FileMetaData::read_from_in_protocol(&mut protocol)
+        .map_err(|e| invalid(&format!("unreadable footer: {e}")))
+}
+
+/// The row groups to keep and the byte ranges that hold their column chunks.
+struct Plan {
+    metadata: FileMetaData,
+    /// The top-level columns to fetch, or `None` for all of them.
+    projection: Option<Vec<String>>,
+    ranges: Vec<Range<u64>>,
+}
+
+impl Plan {
+    /// Plan the read of the row groups that may hold `seasons`, given the
+    /// file's footer and the length of its data section (`data_len`). With
+    /// `columns`, only the chunks of those columns and `season` are fetched;
+    /// names the file does not have are ignored.
+    fn new(
+        mut metadata: FileMetaData,
+        data_len: u64,
+        seasons: &[i32],
+        columns: Option<&[&str]>,
+    ) -> Result<Self> {
+        let projection = columns.map(|columns| {
+            let mut names: Vec<String> = Vec::new();
+            let first = metadata
+                .row_groups
+                .first()
+                .map_or(&[][..], |group| &group.columns);
+            for name in first.iter().filter_map(top_level_name) {
+                let wanted = name == "season" || columns.contains(&name);
+                if wanted && !names.iter().any(|kept| kept == name) {
+                    names.push(name.to_string());
+                }
+            }
+            names
+        });
+
+        metadata
+            .row_groups
+            .retain(|group| may_contain_seasons(group, seasons));
+        metadata.num_rows = metadata.row_groups.iter().map(|group| group.num_rows).sum();
+
+        let mut chunks = Vec::new();
+        for group in &mut metadata.row_groups {
+            for chunk in &mut group.columns {
+                let fetched = fetches(projection.as_deref(), chunk);
+                let Some(meta) = chunk.meta_data.as_mut() else {
+                    return Err(invalid("column chunk without metadata"));
+                };
+                if !fetched {
+                    continue;
+                }
+                // Page indexes and bloom filters are not fetched.
+                chunk.offset_index_offset = None;
+                chunk.offset_index_length = None;
+                chunk.column_index_offset = None;
+                chunk.column_index_length = None;
+                meta.bloom_filter_offset = None;
+                meta.bloom_filter_length = None;
+                chunks.push(chunk_range(meta)?);
+            }
+        }
+
+        chunks.sort_by_key(|range| range.start);
+        if chunks
+            .iter()
+            .any(|range| range.start < MAGIC.len() as u64 || range.end > data_len)
+        {
+            return Err(invalid("column chunk outside the data section"));
+        }
+
+        let mut ranges: Vec<Range<u64>> = Vec::new();
+        for chunk in chunks {
+            match ranges.last_mut() {
+                Some(last) if chunk.start <= last.end + MAX_RANGE_GAP => {
+                    last.end = last.end.max(chunk.end);
+                }
+                _ => ranges.push(chunk),
+            }
+        }
+
+        Ok(Plan {
+            metadata,
+            projection,
+            ranges,
+        })
+    }
+
+    /// Pack the fetched `parts` (each with its original start offset) into a
+    /// new file and read it.
+    fn read(self, parts: &[(u64, Bytes)]) -> Result<DataFrame> {
+        ParquetReader::new(Cursor::new(self.repack(parts)?))
+            .finish()
+            .map_err(|e| invalid(&format!("cannot read the fetched row groups: {e}")))
+    }
+
+    /// Pack the fetched `parts` into a new file whose footer lists just the
+    /// fetched column chunks, at their new offsets.
+    fn repack(mut self, parts: &[(u64, Bytes)]) -> Result<Vec<u8>> {
+        if let Some(projection) = &self.projection {
+            project_schema(&mut self.metadata, projection)?;
+        }
+
+        let len = parts.iter().map(|(_, bytes)| bytes.len()).sum::<usize>();
+        let mut file = Vec::with_capacity(MAGIC.len() + len);
+        file.extend_from_slice(MAGIC);
+        // (original range, offset of its first byte in `file`)
+        let mut moved = Vec::with_capacity(parts.len());
+        for (start, bytes) in parts {
+            moved.push((*start..start + bytes.len() as u64, file.len() as u64));
+            file.extend_from_slice(bytes);
+        }
+        let shift = |offset: u64| -> Result<i64> {
+            moved
+                .iter()
+                .find(|(range, _)| range.contains(&offset))
+                .map(|(range, to)| (to + offset - range.start) as i64 - offset as i64)
+                .ok_or_else(|| invalid("column chunk was not fetched"))
+        };
+
+        for group in &mut self.metadata.row_groups {
+            let mut group_start = None;
+            for chunk in &mut group.columns {
+                let meta = chunk.meta_data.as_mut().expect("checked by Plan::new");
+                let range = chunk_range(meta)?;
+                let delta = shift(range.start)?;
+                if meta
+                    .dictionary_page_offset
+                    .is_some_and(|offset| offset as u64 == range.start)
+                {
+                    meta.dictionary_page_offset = Some(range.start as i64 + delta);
+                }
+                meta.data_page_offset += delta;
+                meta.index_page_offset = None;
+                chunk.file_offset += delta;
+                group_start.get_or_insert(range.start as i64 + delta);
+            }
+            group.file_offset = group_start;
+        }
+
+        append_footer(&mut file, &self.metadata)?;
+        Ok(file)
+    }
+}
+
+/// Cut `metadata` down to the top-level columns in `projection`: their
+/// schema elements, column orders and chunks in every row group. An Arrow
+/// schema stored in the key-value metadata is replaced by one for the kept
+/// columns, so their types read back the same.
+fn project_schema(metadata: &mut FileMetaData, projection: &[String]) -> Result<()> {
+    let arrow_schema = match arrow_schema_key(metadata) {
+        Some(_) => Some(projected_arrow_schema(metadata, projection)?),
+        None => None,
+    };
+
+    let Some((root, fields)) = metadata.schema.split_first() else {
+        return Err(invalid("empty schema"));
+    };
+    let mut schema = vec![root.clone()];
+    // Whether each leaf column, in file order, is kept.
+    let mut kept_leaves = Vec::new();
+    let mut children = 0;
+    let mut index = 0;
+    while index < fields.len() {
+        let len = subtree_len(fields, index)?;
+        let subtree = &fields[index..index + len];
+        let keep = projection.contains(&subtree[0].name);
+        if keep {
+            schema.extend_from_slice(subtree);
+            children += 1;
+        }
+        let leaves = subtree.iter().filter(|e| e.num_children.is_none()).count();
+        kept_leaves.extend(std::iter::repeat_n(keep, leaves));
+        index += len;
+    }
+    schema[0].num_children = Some(children);
+    metadata.schema = schema;
+
+    if let Some(orders) = metadata.column_orders.take() {
+        let orders = orders.into_iter().zip(&kept_leaves);
+        metadata.column_orders = Some(orders.filter(|(_, keep)| **keep).map(|(o, _)| o).collect());
+    }
+    // Index of each kept leaf among the kept ones.
+    let renumbered: Vec<Option<i32>> = kept_leaves
+        .iter()
+        .scan(0, |next, &keep| {
+            let index = keep.then_some(*next);
+            *next += keep as i32;
+            Some(index)
+        })
+        .collect();
+    for group in &mut metadata.row_groups {
+        group
+            .columns
+            .retain(|chunk| fetches(Some(projection), chunk));
+        let sizes = group
+            .columns
+            .iter()
+            .filter_map(|chunk| chunk.meta_data.as_ref());
+        group.total_byte_size = sizes.clone().map(|m| m.total_uncompressed_size).sum();
+        if group.total_compressed_size.is_some() {
+            group.total_compressed_size = Some(sizes.map(|m| m.total_compressed_size).sum());
+        }
+        if let Some(sorting) = group.sorting_columns.as_mut() {
+            sorting.retain_mut(|column| {
+                let index = usize::try_from(column.column_idx).ok();
+                match index.and_then(|i| renumbered.get(i).copied().flatten()) {
+                    Some(index) => {
+                        column.column_idx = index;
+                        true
+                    }
+                    None => false,
+                }
+            });
+        }
+    }
+
+    if let (Some(value), Some(key)) = (arrow_schema, arrow_schema_key(metadata)) {
+        key.value = value;
+    }
+    Ok(())
+}
+
+/// The number of schema elements in the subtree starting at `fields[index]`.
+fn subtree_len(fields: &[SchemaElement], index: usize) -> Result<usize> {
+    let element = fields
+        .get(index)
+        .ok_or_else(|| invalid("truncated schema"))?;
+    let mut len = 1;
+    for _ in 0..element.num_children.unwrap_or(0) {
+        len += subtree_len(fields, index + len)?;
+    }
+    Ok(len)
+}
+
+fn arrow_schema_key(metadata: &mut FileMetaData) -> Option<&mut KeyValue> {
+    metadata
+        .key_value_metadata
+        .as_mut()?
+        .iter_mut()
+        .find(|kv| kv.key == ARROW_SCHEMA_KEY)
+}
+
+/// The encoded Arrow schema of the `projection` columns of the file
+/// described by `metadata`. It is taken from an empty file with the same
+/// footer, read and written back by Polars with just those columns.
+fn projected_arrow_schema(
+    metadata: &FileMetaData,
+    projection: &[String],
+) -> Result<Option<String>> {
+    let mut empty = metadata.clone();
+    empty.row_groups.clear();
+    empty.num_rows = 0;
+    let mut file = MAGIC.to_vec();
+    append_footer(&mut file, &empty)?;
+    let unreadable = |e: PolarsError| invalid(&format!("cannot rewrite the Arrow schema: {e}"));
+    let mut df = ParquetReader::new(Cursor::new(file))
+        .with_columns(Some(projection.to_vec()))
+        .finish()
+        .map_err(unreadable)?;
+
+    let mut written = Vec::new();
+    ParquetWriter::new(&mut written)
+        .finish(&mut df)
+        .map_err(unreadable)?;
+    let footer = footer_range(&Part {
+        start: 0,
+        file_len: written.len() as u64,
+        bytes: Bytes::from(written),
+        validator: None,
+        metadata: CacheMetadata::default(),
+    })?
+    .map_err(|_| invalid("unreadable rewritten footer"))?;
+    let mut written = decode_footer(&footer)?;
+    Ok(arrow_schema_key(&mut written).and_then(|kv| kv.value.take()))
+}
+
+/// Append `metadata` as the footer that ends a Parquet file.
+fn append_footer(file: &mut Vec<u8>, metadata: &FileMetaData) -> Result<()> {
+    let footer_start = file.len();
+    let mut protocol = TCompactOutputProtocol::new(&mut *file);
+    metadata
+        .write_to_out_protocol(&mut protocol)
+        .map_err(|e| invalid(&format!("cannot rewrite footer: {e}")))?;
+    let footer_len = (file.len() - footer_start) as u32;
+    file.extend_from_slice(&footer_len.to_le_bytes());
+    file.extend_from_slice(MAGIC);
+    Ok(())
+}
+
+/// The name of the top-level column that `chunk` belongs to.
+fn top_level_name(chunk: &ColumnChunk) -> Option<&str> {
+    chunk
+        .meta_data
+        .as_ref()?
+        .path_in_schema
+        .first()
+        .map(String::as_str)
+}
+
+/// Whether `chunk` is fetched under `projection`.
+fn fetches(projection: Option<&[String]>, chunk: &ColumnChunk) -> bool {
+    match projection {
+        Some(names) => top_level_name(chunk).is_some_and(|name| names.iter().any(|n| n == name)),
+        None => true,
+    }
+}
+
+/// Whether the `season` statistics of `group` overlap `seasons`. Row groups
+/// without usable statistics are always kept.
+fn may_contain_seasons(group: &RowGroup, seasons: &[i32]) -> bool {
+    let Some(meta) = group
+        .columns
+        .iter()
+        .filter_map(|chunk| chunk.meta_data.as_ref())
+        .find(|meta| meta.path_in_schema == ["season"])
+    else {
+        return true;
+    };
+    let Some(stats) = &meta.statistics else {
+        return true;
+    };
+    let min = stats.min_value.as_deref().or(stats.min.as_deref());
+    let max = stats.max_value.as_deref().or(stats.max.as_deref());
+    let (Some(min), Some(max)) = (
+        min.and_then(|v| decode_int(meta.type_, v)),
+        max.and_then(|v| decode_int(meta.type_, v)),
+    ) else {
+        return true;
+    };
+    seasons
+        .iter()
+        .any(|&season| (min..=max).contains(&(season as i64)))
+}
+
+/// A PLAIN-encoded integer statistic.
+fn decode_int(physical_type: Type, bytes: &[u8]) -> Option<i64> {
+    match physical_type {
+        Type::INT32 => Some(i32::from_le_bytes(bytes.try_into().ok()?) as i64),
+        Type::INT64 => Some(i64::from_le_bytes(bytes.try_into().ok()?)),
+        _ => None,
+    }
+}
+
+/// The bytes of a column chunk, starting at its dictionary page if it has one.
+fn chunk_range(meta: &ColumnMetaData) -> Result<Range<u64>> {
+    let start = match meta.dictionary_page_offset {
+        Some(offset) if offset > 0 && offset < meta.data_page_offset => offset,
+        _ => meta.data_page_offset,
+    };
+    let (Ok(start), Ok(len)) = (
+        u64::try_from(start),
+        u64::try_from(meta.total_compressed_size),
+    ) else {
+        return Err(invalid("negative column chunk offset"));
+    };
+    Ok(start..start + len)
+}
+
+fn invalid(message: &str) -> NflReadError {
+    NflReadError::Io(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        message.to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::RowAccessor;
+
+    use super::*;
+
+    #[test]
+    fn test_repacked_projection_is_valid_parquet() {
+        let mut df = df! {
+            "season" => [2022i32, 2022, 2023, 2023],
+            "plays" => [
+                Series::new("".into(), [1i32, 2]),
+                Series::new("".into(), [3i32]),
+                Series::new("".into(), [4i32, 5]),
+                Series::new("".into(), [6i32]),
+            ],
+            "team" => ["KC", "PHI", "KC", "BUF"],
+            "epa" => [0.5, -0.25, 1.0, 0.1],
+        }
+        .unwrap();
+        let team = df.column("team").unwrap();
+        let team = team
+            .cast(&DataType::Categorical(None, Default::default()))
+            .unwrap();
+        df.with_column(team).unwrap();
+        let mut file = Vec::new();
+        ParquetWriter::new(&mut file)
+            .with_row_group_size(Some(2))
+            .finish(&mut df)
+            .unwrap();
+
+        let tail = Part {
+            start: 0,
+            file_len: file.len() as u64,
+            bytes: Bytes::from(file.clone()),
+            validator: None,
+            metadata: CacheMetadata::default(),
+        };
+        let footer = footer_range(&tail).unwrap().unwrap();
+        let data_len = file.len() as u64 - 8 - footer.len() as u64;
+        let columns: &[&str] = &["epa", "team"];
+        let plan = Plan::new(
+            decode_footer(&footer).unwrap(),
+            data_len,
+            &[2023],
+            Some(columns),
+        );
+        let plan = plan.unwrap();
+        let parts: Vec<(u64, Bytes)> = plan
+            .ranges
+            .iter()
+            .map(|range| {
+                let bytes = &file[range.start as usize..range.end as usize];
+                (range.start, Bytes::copy_from_slice(bytes))
+            })
+            .collect();
+        let repacked = plan.repack(&parts).unwrap();
+
+        // An independent reader sees only the fetched row group and columns.
+        let reader = SerializedFileReader::new(Bytes::from(repacked.clone())).unwrap();
+        let schema = reader.metadata().file_metadata().schema_descr_ptr();
+        let names: Vec<String> = schema.columns().iter().map(|c| c.path().string()).collect();
+        assert_eq!(names, ["season", "team", "epa"]);
+        assert_eq!(reader.metadata().num_row_groups(), 1);
+        let rows: Vec<_> = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| row.unwrap())
+            .collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].get_int(0).unwrap(), 2023);
+        assert_eq!(rows[1].get_string(1).unwrap(), "BUF");
+        assert_eq!(rows[1].get_double(2).unwrap(), 0.1);
+
+        // The rewritten Arrow schema keeps the kept columns' types.
+        let read = ParquetReader::new(Cursor::new(repacked)).finish().unwrap();
+        let expected = df.slice(2, 2).select(["season", "team", "epa"]).unwrap();
+        assert!(read.equals(&expected), "{read}");
+        assert_eq!(
+            read.column("team").unwrap().dtype(),
+            expected.column("team").unwrap().dtype()
+        );
+    }
+}
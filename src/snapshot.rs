@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::cache::write_file_atomically;
+use crate::catalog::datasets;
+use crate::config::get_config;
+use crate::downloader::{DataFormat, build_url, download_bytes, run_concurrently};
+use crate::error::Result;
+
+/// A file written by [`snapshot`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotFile {
+    pub dataset: String,
+    /// Path relative to the snapshot directory, e.g.
+    /// `nflverse-data/pbp/play_by_play_2023.parquet`.
+    pub path: String,
+    pub url: String,
+    pub size: u64,
+    /// Hex MD5 digest of the file contents.
+    pub md5: String,
+}
+
+/// A file that could not be downloaded, e.g. a season not yet published.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotFailure {
+    pub dataset: String,
+    pub url: String,
+    pub error: String,
+}
+
+/// The manifest written to `manifest.toml` in the snapshot directory.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    /// RFC 3339 timestamp of when the snapshot was taken.
+    pub created_at: String,
+    pub files: Vec<SnapshotFile>,
+    pub failures: Vec<SnapshotFailure>,
+}
+
+/// Download every dataset the crate knows about into `dir`.
+///
+/// Files are laid out as `<dir>/<repository>/<release path>`, so a snapshot
+/// directory can be used as a local data source, e.g.
+/// `NFLREADRUST_NFLVERSE_DATA_URL=<dir>/nflverse-data`. Per-season datasets
+/// are fetched for every season from their first season to the current one,
+/// and parameterized datasets for every parameter value.
+///
+/// Files that fail to download (typically seasons not yet published) are
+/// recorded in the manifest's `failures` rather than aborting the snapshot.
+/// The manifest is written to `<dir>/manifest.toml` and returned. Every file
+/// is written to a temporary file and renamed into place, so an interrupted
+/// snapshot never leaves a truncated file behind.
+pub fn snapshot(dir: &Path) -> Result<SnapshotManifest> {
+    let mut targets = Vec::new();
+    for dataset in datasets() {
+        for (path, format) in dataset.all_files() {
+            let url = build_url(dataset.repository, &path, format);
+            let file = format!(
+                "{}/{}",
+                dataset.repository.name(),
+                with_extension(&path, format)
+            );
+            targets.push((dataset.name, url, file));
+        }
+    }
+
+    let config = get_config();
+    let results = run_concurrently(&targets, config.download_concurrency, false, |target| {
+        let (_, url, file) = target;
+        let bytes = download_bytes(url)?;
+        let dest: PathBuf = dir.join(file);
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        write_file_atomically(&dest, &bytes)?;
+        Ok((bytes.len() as u64, format!("{:x}", md5::compute(&bytes))))
+    });
+
+    let mut manifest = SnapshotManifest {
+        created_at: chrono::Utc::now().to_rfc3339(),
+        ..SnapshotManifest::default()
+    };
+    for ((dataset, url, file), result) in targets.into_iter().zip(results) {
+        match result {
+            Some(Ok((size, md5))) => manifest.files.push(SnapshotFile {
+                dataset: dataset.to_string(),
+                path: file,
+                url,
+                size,
+                md5,
+            }),
+            Some(Err(e)) => manifest.failures.push(SnapshotFailure {
+                dataset: dataset.to_string(),
+                url,
+                error: e.to_string(),
+            }),
+            None => unreachable!("snapshot downloads continue after failures"),
+        }
+    }
+
+    std::fs::create_dir_all(dir)?;
+    let contents = toml::to_string(&manifest)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    write_file_atomically(&dir.join("manifest.toml"), contents.as_bytes())?;
+
+    Ok(manifest)
+}
+
+fn with_extension(path: &str, format: DataFormat) -> String {
+    if path.ends_with(".parquet") || path.ends_with(".csv") {
+        return path.to_string();
+    }
+    match format {
+        DataFormat::Parquet => format!("{path}.parquet"),
+        DataFormat::Csv => format!("{path}.csv"),
+    }
+}
@@ -15,7 +15,11 @@ pub fn get_current_season(roster: bool) -> i32 {
     } else {
         let labor_day = first_monday_in_september(year);
         let thursday_after = labor_day + chrono::Days::new(3);
-        if today < thursday_after { year - 1 } else { year }
+        if today < thursday_after {
+            year - 1
+        } else {
+            year
+        }
     }
 }
 
@@ -32,6 +36,17 @@ pub fn get_current_week(use_date: bool) -> crate::error::Result<i32> {
     }
 }
 
+/// Async version of [`get_current_week`].
+pub async fn get_current_week_async(use_date: bool) -> crate::error::Result<i32> {
+    if use_date {
+        Ok(get_current_week_from_date())
+    } else {
+        let season = get_current_season(false);
+        let df = crate::loaders::load_schedules::load_schedules_async(season).await?;
+        current_week_from_schedule(&df)
+    }
+}
+
 fn get_current_week_from_date() -> i32 {
     let today = Local::now().date_naive();
     let season = get_current_season(false);
@@ -48,8 +63,11 @@ fn get_current_week_from_date() -> i32 {
 
 fn get_current_week_from_schedule() -> crate::error::Result<i32> {
     let season = get_current_season(false);
-    let df = crate::loaders::load_schedules::load_schedules(Some(vec![season]))?;
+    let df = crate::loaders::load_schedules::load_schedules(season)?;
+    current_week_from_schedule(&df)
+}
 
+fn current_week_from_schedule(df: &polars::prelude::DataFrame) -> crate::error::Result<i32> {
     // Find the first week where result is null (game not yet played)
     let mask = df.column("result")?.is_null();
     let filtered = df.filter(&mask)?;
@@ -57,21 +75,11 @@ fn get_current_week_from_schedule() -> crate::error::Result<i32> {
     if filtered.height() == 0 {
         // All games played - return max week
         let week_col = df.column("week")?;
-        let max_week = week_col
-            .i32()?
-            .into_iter()
-            .flatten()
-            .max()
-            .unwrap_or(22);
+        let max_week = week_col.i32()?.into_iter().flatten().max().unwrap_or(22);
         Ok(max_week)
     } else {
         let week_col = filtered.column("week")?;
-        let min_week = week_col
-            .i32()?
-            .into_iter()
-            .flatten()
-            .min()
-            .unwrap_or(1);
+        let min_week = week_col.i32()?.into_iter().flatten().min().unwrap_or(1);
         Ok(min_week)
     }
 }
@@ -0,0 +1,771 @@
+//! Cache behavior tests. These change the global configuration, so each test
+//! holds the config lock and they live in their own test binary.
+
+mod common;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
+
+use common::{Response, TestServer, lock_config, parquet_bytes, sample_df};
+use nflreadrust::cache::{CacheMetadata, cache_get, cache_get_stale, cache_set, make_cache_key};
+use nflreadrust::config::{get_config, update_config};
+use nflreadrust::downloader::{
+    DataFormat, Repository, download_all, download_all_async, download_dataframe,
+};
+use nflreadrust::*;
+use polars::prelude::{col, df, lit};
+
+/// Records the library's log messages, for asserting on warnings.
+struct TestLogger(Mutex<Vec<String>>);
+
+impl log::Log for TestLogger {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("{}: {}", record.level(), record.args()));
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: TestLogger = TestLogger(Mutex::new(Vec::new()));
+
+/// Drain the messages logged since the last call.
+fn take_logs() -> Vec<String> {
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(log::LevelFilter::Trace);
+    }
+    std::mem::take(&mut *LOGGER.0.lock().unwrap())
+}
+
+/// Install a filesystem-cache config rooted in a fresh temporary directory.
+fn filesystem_config(name: &str, cache_duration: u64) -> (MutexGuard<'static, ()>, PathBuf) {
+    let guard = lock_config();
+    let cache_dir =
+        std::env::temp_dir().join(format!("nflreadrust-test-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&cache_dir);
+
+    update_config(Config {
+        cache_mode: CacheMode::Filesystem,
+        cache_dir: cache_dir.clone(),
+        cache_duration,
+        ..Config::default()
+    });
+    (guard, cache_dir)
+}
+
+#[test]
+fn test_filesystem_cache_revalidates_with_etag() {
+    let (_guard, _dir) = filesystem_config("etag", 0);
+
+    let body = parquet_bytes(&sample_df());
+    let server = TestServer::start(move |request, _| {
+        if request.headers.get("if-none-match").map(String::as_str) == Some("\"v1\"") {
+            Response::status(304)
+        } else {
+            Response::ok(body.clone()).header("ETag", "\"v1\"")
+        }
+    });
+    let url = format!("{}/pbp/play_by_play_2023.parquet", server.url);
+
+    let first = download_dataframe(&url, DataFormat::Parquet).unwrap();
+    let second = download_dataframe(&url, DataFormat::Parquet).unwrap();
+
+    assert_eq!(server.hits(), 2);
+    let requests = server.requests.lock().unwrap();
+    assert!(!requests[0].headers.contains_key("if-none-match"));
+    assert_eq!(requests[1].headers["if-none-match"], "\"v1\"");
+    assert!(first.equals(&second));
+}
+
+#[test]
+fn test_revalidation_stores_the_new_validators() {
+    let (_guard, _dir) = filesystem_config("revalidate", 0);
+
+    let body = parquet_bytes(&sample_df());
+    let server = TestServer::start(move |request, _| {
+        if request.headers.contains_key("if-none-match") {
+            Response::status(304)
+                .header("ETag", "\"v2\"")
+                .header("Last-Modified", "Tue, 03 Sep 2024 00:00:00 GMT")
+        } else {
+            Response::ok(body.clone()).header("ETag", "\"v1\"")
+        }
+    });
+    let url = format!("{}/pbp/play_by_play_2023.parquet", server.url);
+
+    for _ in 0..3 {
+        download_dataframe(&url, DataFormat::Parquet).unwrap();
+    }
+
+    let requests = server.requests.lock().unwrap();
+    assert_eq!(requests[1].headers["if-none-match"], "\"v1\"");
+    assert!(!requests[1].headers.contains_key("if-modified-since"));
+    assert_eq!(requests[2].headers["if-none-match"], "\"v2\"");
+    assert_eq!(
+        requests[2].headers["if-modified-since"],
+        "Tue, 03 Sep 2024 00:00:00 GMT"
+    );
+}
+
+#[test]
+fn test_async_download_shares_the_filesystem_cache() {
+    let (_guard, _dir) = filesystem_config("async", 0);
+
+    let body = parquet_bytes(&sample_df());
+    let server = TestServer::start(move |request, _| {
+        if request.headers.contains_key("if-none-match") {
+            Response::status(304)
+        } else {
+            Response::ok(body.clone()).header("ETag", "\"v1\"")
+        }
+    });
+    let url = format!("{}/pbp/play_by_play_2023.parquet", server.url);
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let first = runtime
+        .block_on(downloader::download_dataframe_async(
+            &url,
+            DataFormat::Parquet,
+        ))
+        .unwrap();
+    // The sync and async versions revalidate the same entry.
+    let second = download_dataframe(&url, DataFormat::Parquet).unwrap();
+    let third = runtime
+        .block_on(downloader::download_dataframe_async(
+            &url,
+            DataFormat::Parquet,
+        ))
+        .unwrap();
+
+    assert_eq!(server.hits(), 3);
+    assert!(first.equals(&second) && second.equals(&third));
+    let requests = server.requests.lock().unwrap();
+    assert!(
+        requests[1..]
+            .iter()
+            .all(|r| r.headers["if-none-match"] == "\"v1\"")
+    );
+}
+
+#[test]
+fn test_filesystem_cache_refetches_when_changed() {
+    let (_guard, _dir) = filesystem_config("changed", 0);
+
+    let body = parquet_bytes(&sample_df());
+    let server = TestServer::start(move |_, hit| {
+        let last_modified = format!("Mon, 0{} Sep 2024 00:00:00 GMT", hit + 1);
+        Response::ok(body.clone()).header("Last-Modified", &last_modified)
+    });
+    let url = format!("{}/schedules/games.parquet", server.url);
+
+    download_dataframe(&url, DataFormat::Parquet).unwrap();
+    let df = download_dataframe(&url, DataFormat::Parquet).unwrap();
+
+    assert_eq!(df.height(), 3);
+    let requests = server.requests.lock().unwrap();
+    assert_eq!(
+        requests[1].headers["if-modified-since"],
+        "Mon, 01 Sep 2024 00:00:00 GMT"
+    );
+}
+
+#[test]
+fn test_offline_mode_serves_expired_cache_and_reports_misses() {
+    let (_guard, dir) = filesystem_config("offline", 0);
+
+    let body = parquet_bytes(&sample_df());
+    let server = TestServer::start(move |_, _| Response::ok(body.clone()));
+    let cached_url = format!("{}/players/players.parquet", server.url);
+    let missing_url = format!("{}/trades/trades.parquet", server.url);
+    download_dataframe(&cached_url, DataFormat::Parquet).unwrap();
+
+    update_config(Config {
+        cache_mode: CacheMode::Filesystem,
+        cache_dir: dir,
+        cache_duration: 0,
+        offline: true,
+        ..Config::default()
+    });
+    let df = download_dataframe(&cached_url, DataFormat::Parquet).unwrap();
+    assert_eq!(df.height(), 3);
+
+    match download_dataframe(&missing_url, DataFormat::Parquet) {
+        Err(NflReadError::NotCached(url)) => assert_eq!(url, missing_url),
+        other => panic!("expected NotCached, got {other:?}"),
+    }
+
+    // With the cache turned off, not even the filesystem entry is read.
+    update_config(Config {
+        cache_mode: CacheMode::Off,
+        ..get_config()
+    });
+    assert!(matches!(
+        download_dataframe(&cached_url, DataFormat::Parquet),
+        Err(NflReadError::NotCached(_))
+    ));
+    assert_eq!(server.hits(), 1, "offline loads must not touch the network");
+}
+
+#[test]
+fn test_stale_if_error_falls_back_to_expired_entry() {
+    let (_guard, dir) = filesystem_config("stale", 0);
+
+    let body = parquet_bytes(&sample_df());
+    let server = TestServer::start(move |_, hit| {
+        if hit == 0 {
+            Response::ok(body.clone())
+        } else {
+            Response::status(404)
+        }
+    });
+    let url = format!("{}/injuries/injuries_2023.parquet", server.url);
+    download_dataframe(&url, DataFormat::Parquet).unwrap();
+
+    // Without the opt-in the failure surfaces.
+    assert!(download_dataframe(&url, DataFormat::Parquet).is_err());
+
+    update_config(Config {
+        cache_mode: CacheMode::Filesystem,
+        cache_dir: dir,
+        cache_duration: 0,
+        stale_if_error: true,
+        ..Config::default()
+    });
+    take_logs();
+    let (df, freshness) = track_freshness(|| download_dataframe(&url, DataFormat::Parquet));
+    assert_eq!(df.unwrap().height(), 3);
+    assert!(freshness.stale);
+
+    let stale: Vec<String> = take_logs()
+        .into_iter()
+        .filter(|message| message.contains("using cached copy"))
+        .collect();
+    assert_eq!(stale.len(), 1);
+    assert!(stale[0].starts_with(&format!("WARN: failed to download {url} ")));
+}
+
+#[test]
+fn test_track_freshness_covers_concurrent_and_async_downloads() {
+    let (_guard, dir) = filesystem_config("freshness", 3600);
+
+    let body = parquet_bytes(&sample_df());
+    let server = TestServer::start(move |request, _| {
+        if request.path.ends_with("2023.parquet") {
+            Response::ok(body.clone())
+        } else {
+            Response::status(404)
+        }
+    });
+    let urls: Vec<String> = [2022, 2023]
+        .iter()
+        .map(|season| format!("{}/injuries/injuries_{season}.parquet", server.url))
+        .collect();
+    let stale_df = df!("season" => [2022]).unwrap();
+    let metadata = CacheMetadata {
+        url: urls[0].clone(),
+        ..CacheMetadata::default()
+    };
+    cache_set(&make_cache_key(&urls[0]), &stale_df, &metadata);
+
+    update_config(Config {
+        cache_mode: CacheMode::Filesystem,
+        cache_dir: dir,
+        cache_duration: 0,
+        stale_if_error: true,
+        download_concurrency: 2,
+        ..Config::default()
+    });
+
+    // Only the expired 2022 entry is served stale; 2023 downloads fine.
+    let (dfs, freshness) = track_freshness(|| download_all(&urls, DataFormat::Parquet));
+    assert_eq!(dfs.unwrap().len(), 2);
+    assert!(freshness.stale);
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let (dfs, freshness) = runtime.block_on(track_freshness_async(download_all_async(
+        &urls,
+        DataFormat::Parquet,
+    )));
+    assert_eq!(dfs.unwrap().len(), 2);
+    assert!(freshness.stale);
+
+    // A load that downloads everything is fresh.
+    let (df, freshness) = track_freshness(|| download_dataframe(&urls[1], DataFormat::Parquet));
+    df.unwrap();
+    assert_eq!(freshness, Freshness::default());
+}
+
+#[test]
+fn test_cache_entries_prune_and_clear_by_dataset() {
+    let (_guard, _dir) = filesystem_config("entries", 3600);
+    clear_cache(None);
+
+    let body = parquet_bytes(&sample_df());
+    let server = TestServer::start(move |_, _| Response::ok(body.clone()));
+    let mut config = get_config();
+    config.base_urls = HashMap::from([(Repository::NflverseData, server.url.clone())]);
+    update_config(config.clone());
+
+    load_pbp([2022, 2023], None).unwrap();
+    load_teams().unwrap();
+
+    let entries = cache_entries();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(
+        entries.iter().filter(|e| e.dataset == Some("pbp")).count(),
+        2
+    );
+    assert!(entries.iter().all(|e| !e.is_expired() && e.size > 0));
+    let teams = entries.iter().find(|e| e.dataset == Some("teams")).unwrap();
+    assert_eq!(
+        teams.url.as_deref(),
+        Some(format!("{}/teams/teams_colors_logos.parquet", server.url).as_str())
+    );
+
+    assert_eq!(clear_dataset_cache("pbp").unwrap(), 2);
+    assert!(matches!(
+        clear_dataset_cache("play_by_play"),
+        Err(NflReadError::InvalidParameter(_))
+    ));
+    let remaining = cache_entries();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].dataset, Some("teams"));
+
+    config.cache_duration = 0;
+    update_config(config);
+    assert_eq!(prune_cache(), 1);
+    assert!(cache_entries().is_empty());
+}
+
+#[test]
+fn test_cache_keys_follow_release_paths() {
+    let (_guard, dir) = filesystem_config("keys", 3600);
+    clear_cache(None);
+
+    assert_eq!(
+        make_cache_key(
+            "https://github.com/nflverse/nflverse-data/releases/download/pbp/play_by_play_2023.parquet"
+        ),
+        "nflverse-data/pbp/play_by_play_2023"
+    );
+    assert_eq!(
+        make_cache_key("https://github.com/dynastyprocess/data/raw/master/files/db_playerids.csv"),
+        "dynastyprocess/db_playerids.csv"
+    );
+    let key = make_cache_key("https://example.com:8080/v1:raw/data.csv?v=1");
+    assert!(
+        key.starts_with("example.com_8080/v1_raw/data.csv_"),
+        "{key}"
+    );
+
+    let body = parquet_bytes(&sample_df());
+    let server = TestServer::start(move |_, _| Response::ok(body.clone()));
+    let mut config = get_config();
+    config.base_urls = HashMap::from([(Repository::NflverseData, server.url.clone())]);
+    update_config(config);
+
+    load_pbp(2023, None).unwrap();
+    load_teams().unwrap();
+    let pbp = dir.join("nflverse-data/pbp/play_by_play_2023.parquet");
+    assert!(pbp.exists());
+    assert!(
+        dir.join("nflverse-data/pbp/play_by_play_2023.meta.toml")
+            .exists()
+    );
+
+    let mut keys: Vec<String> = cache_entries().into_iter().map(|e| e.key).collect();
+    keys.sort();
+    assert_eq!(
+        keys,
+        [
+            "nflverse-data/pbp/play_by_play_2023",
+            "nflverse-data/teams/teams_colors_logos",
+        ]
+    );
+
+    clear_cache(Some("pbp/"));
+    assert!(!pbp.exists());
+    assert_eq!(cache_entries().len(), 1);
+
+    // A pattern naming a data file takes its sidecar along, as does one
+    // matching a sidecar whose data file is gone.
+    let teams = dir.join("nflverse-data/teams/teams_colors_logos");
+    clear_cache(Some("teams_colors_logos.parquet"));
+    assert!(!teams.with_extension("meta.toml").exists());
+    load_teams().unwrap();
+    std::fs::remove_file(teams.with_extension("parquet")).unwrap();
+    clear_cache(Some("teams/teams_colors_logos"));
+    assert!(!teams.with_extension("meta.toml").exists());
+}
+
+#[test]
+fn test_memory_cache_evicts_least_recently_used() {
+    let _guard = lock_config();
+    let size = sample_df().estimated_size();
+    update_config(Config {
+        cache_mode: CacheMode::Memory,
+        memory_cache_max_bytes: size * 2 + size / 2,
+        ..Config::default()
+    });
+    clear_cache(None);
+
+    let metadata = CacheMetadata::default();
+    cache_set("lru/a", &sample_df(), &metadata);
+    cache_set("lru/b", &sample_df(), &metadata);
+    assert!(cache_get("lru/a").is_some());
+    cache_set("lru/c", &sample_df(), &metadata);
+
+    assert!(cache_get("lru/a").is_some());
+    assert!(cache_get("lru/b").is_none());
+    assert!(cache_get("lru/c").is_some());
+
+    // A frame larger than the whole budget is not cached, and says so.
+    update_config(Config {
+        memory_cache_max_bytes: size / 2,
+        ..get_config()
+    });
+    take_logs();
+    cache_set("lru/big", &sample_df(), &metadata);
+    assert!(cache_get("lru/big").is_none());
+    assert!(
+        take_logs()
+            .iter()
+            .any(|m| m.starts_with("WARN: not caching lru/big"))
+    );
+
+    update_config(Config {
+        cache_mode: CacheMode::Memory,
+        cache_duration: 0,
+        ..Config::default()
+    });
+    assert!(cache_get("lru/a").is_none());
+    // Expired entries were purged, not just skipped.
+    assert!(cache_get_stale("lru/c").is_none());
+}
+
+#[test]
+fn test_disk_quota_evicts_least_recently_used() {
+    let (_guard, dir) = filesystem_config("quota", 3600);
+    let metadata = CacheMetadata::default();
+    cache_set("quota/a", &sample_df(), &metadata);
+    cache_set("quota/b", &sample_df(), &metadata);
+    let size = cache_entries()[0].size;
+
+    let mut config = get_config();
+    config.disk_cache_max_bytes = size * 2 + size / 2;
+    update_config(config.clone());
+
+    // Backdate both entries' last use, then read `a` so `b` is the LRU entry.
+    let past = std::time::SystemTime::now() - std::time::Duration::from_secs(600);
+    for key in ["a", "b"] {
+        let file = std::fs::File::options()
+            .write(true)
+            .open(dir.join(format!("quota/{key}.parquet")))
+            .unwrap();
+        file.set_times(std::fs::FileTimes::new().set_accessed(past))
+            .unwrap();
+    }
+    assert!(cache_get("quota/a").is_some());
+    cache_set("quota/c", &sample_df(), &metadata);
+
+    let mut keys: Vec<String> = cache_entries().into_iter().map(|e| e.key).collect();
+    keys.sort();
+    assert_eq!(keys, ["quota/a", "quota/c"]);
+    assert!(!dir.join("quota/b.meta.toml").exists());
+    let dir_size = |dir: &std::path::Path| -> u64 {
+        std::fs::read_dir(dir.join("quota"))
+            .unwrap()
+            .map(|entry| entry.unwrap().metadata().unwrap().len())
+            .sum()
+    };
+    assert!(dir_size(&dir) <= config.disk_cache_max_bytes);
+
+    // Sidecars and leftover temporary files count toward the quota too.
+    let temp = dir.join("quota/x.parquet.1.0.tmp");
+    std::fs::write(&temp, vec![0u8; size as usize]).unwrap();
+    cache_set("quota/d", &sample_df(), &metadata);
+    let keys: Vec<String> = cache_entries().into_iter().map(|e| e.key).collect();
+    assert_eq!(keys, ["quota/d"]);
+    assert!(dir_size(&dir) <= config.disk_cache_max_bytes);
+    std::fs::remove_file(temp).unwrap();
+
+    config.cache_duration = 0;
+    update_config(config);
+    assert_eq!(prune_cache(), 1);
+    assert!(!dir.join("quota").exists());
+    assert!(dir.exists());
+}
+
+#[test]
+fn test_disk_quota_removes_orphaned_files() {
+    let (_guard, dir) = filesystem_config("orphans", 3600);
+    let metadata = CacheMetadata::default();
+    cache_set("orphans/a", &sample_df(), &metadata);
+    let size = std::fs::metadata(dir.join("orphans/a.parquet"))
+        .unwrap()
+        .len()
+        + std::fs::metadata(dir.join("orphans/a.meta.toml"))
+            .unwrap()
+            .len();
+
+    let mut config = get_config();
+    config.disk_cache_max_bytes = size * 2 + size / 2;
+    update_config(config.clone());
+
+    // Sidecars without data files and a temporary file abandoned two hours
+    // ago, together larger than the quota.
+    let sidecar = std::fs::read(dir.join("orphans/a.meta.toml")).unwrap();
+    for i in 0..4 {
+        let mut padded = sidecar.clone();
+        padded.resize(size as usize, b'\n');
+        std::fs::write(dir.join(format!("orphans/gone{i}.meta.toml")), padded).unwrap();
+    }
+    let temp = dir.join("orphans/x.parquet.1.0.tmp");
+    std::fs::write(&temp, vec![0u8; size as usize]).unwrap();
+    let past = std::time::SystemTime::now() - std::time::Duration::from_secs(7200);
+    let file = std::fs::File::options().write(true).open(&temp).unwrap();
+    file.set_times(std::fs::FileTimes::new().set_modified(past))
+        .unwrap();
+    drop(file);
+
+    cache_set("orphans/b", &sample_df(), &metadata);
+    let mut keys: Vec<String> = cache_entries().into_iter().map(|e| e.key).collect();
+    keys.sort();
+    assert_eq!(keys, ["orphans/a", "orphans/b"]);
+    assert!(!temp.exists());
+    let files: Vec<String> = std::fs::read_dir(dir.join("orphans"))
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    assert_eq!(files.len(), 4, "{files:?}");
+    let total: u64 = std::fs::read_dir(dir.join("orphans"))
+        .unwrap()
+        .map(|entry| entry.unwrap().metadata().unwrap().len())
+        .sum();
+    assert!(total <= config.disk_cache_max_bytes);
+}
+
+#[test]
+fn test_filesystem_cache_writes_atomically_and_discards_corrupt_entries() {
+    let (_guard, dir) = filesystem_config("atomic", 3600);
+    let metadata = CacheMetadata {
+        url: "https://example.com/atomic.parquet".to_string(),
+        ..CacheMetadata::default()
+    };
+
+    std::thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|| {
+                cache_set("atomic/shared", &sample_df(), &metadata);
+                assert!(cache_get("atomic/shared").is_some());
+            });
+        }
+    });
+    let files: Vec<String> = std::fs::read_dir(dir.join("atomic"))
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    assert_eq!(files.len(), 2, "{files:?}");
+    assert!(cache_get("atomic/shared").unwrap().equals(&sample_df()));
+
+    // A truncated file is dropped on read rather than returned as an error.
+    let path = dir.join("atomic/shared.parquet");
+    let bytes = std::fs::read(&path).unwrap();
+    std::fs::write(&path, &bytes[..bytes.len() / 2]).unwrap();
+    assert!(cache_get("atomic/shared").is_none());
+    assert!(!path.exists());
+    assert!(!dir.join("atomic/shared.meta.toml").exists());
+}
+
+#[test]
+fn test_raw_cache_stores_downloads_verbatim() {
+    let (_guard, dir) = filesystem_config("raw", 3600);
+    let mut config = get_config();
+    config.cache_raw = true;
+    update_config(config);
+
+    let csv = b"gsis_id,name\n00-0033873,Patrick Mahomes\n00-0036355,Justin Herbert\n".to_vec();
+    let parquet = parquet_bytes(&sample_df());
+    let (csv_body, parquet_body) = (csv.clone(), parquet.clone());
+    let server = TestServer::start(move |request, _| {
+        if request.path.ends_with(".csv") {
+            Response::ok(csv_body.clone())
+        } else {
+            Response::ok(parquet_body.clone())
+        }
+    });
+
+    let csv_url = format!("{}/files/db_playerids.csv", server.url);
+    let parquet_url = format!("{}/pbp/play_by_play_2023.parquet", server.url);
+    let fresh = download_dataframe(&csv_url, DataFormat::Csv).unwrap();
+    download_dataframe(&parquet_url, DataFormat::Parquet).unwrap();
+
+    let csv_path = dir.join(format!("{}.raw", make_cache_key(&csv_url)));
+    let parquet_path = dir.join(format!("{}.parquet", make_cache_key(&parquet_url)));
+    assert_eq!(std::fs::read(&csv_path).unwrap(), csv);
+    assert_eq!(std::fs::read(&parquet_path).unwrap(), parquet);
+    let entry = cache_entries()
+        .into_iter()
+        .find(|e| e.path == csv_path)
+        .unwrap();
+    assert_eq!(entry.md5, Some(format!("{:x}", md5::compute(&csv))));
+
+    let cached = download_dataframe(&csv_url, DataFormat::Csv).unwrap();
+    assert_eq!(server.hits(), 2);
+    assert!(cached.equals(&fresh));
+
+    // Same length, different bytes: only the checksum can catch this.
+    let mut tampered = csv.clone();
+    tampered[20] = b'9';
+    std::fs::write(&csv_path, tampered).unwrap();
+    assert!(cache_get(&make_cache_key(&csv_url)).is_none());
+    assert!(!csv_path.exists());
+    download_dataframe(&csv_url, DataFormat::Csv).unwrap();
+    assert_eq!(server.hits(), 3);
+
+    // A failed write keeps the entry it would have replaced.
+    let key = make_cache_key(&csv_url);
+    let blocker = dir.join(format!("{key}.parquet"));
+    std::fs::create_dir_all(blocker.join("occupied")).unwrap();
+    update_config(Config {
+        cache_raw: false,
+        ..get_config()
+    });
+    cache_set(&key, &sample_df(), &CacheMetadata::default());
+    assert_eq!(std::fs::read(&csv_path).unwrap(), csv);
+    assert!(cache_get(&key).unwrap().equals(&fresh));
+}
+
+#[test]
+fn test_scan_reads_cached_parquet_lazily() {
+    let (_guard, dir) = filesystem_config("scan", 3600);
+    let server = TestServer::start(move |request, _| {
+        let stem = request.path.trim_end_matches(".parquet");
+        let season: i32 = stem.rsplit('_').next().unwrap().parse().unwrap();
+        let df = df! {
+            "season" => [season; 3],
+            "week" => [1i32, 1, 2],
+            "posteam" => ["KC", "PHI", "KC"],
+            "epa" => [0.5, -0.25, 1.0],
+        }
+        .unwrap();
+        Response::ok(parquet_bytes(&df))
+    });
+    let mut config = get_config();
+    config.base_urls = HashMap::from([(Repository::NflverseData, server.url.clone())]);
+    update_config(config.clone());
+
+    let scan = || {
+        scan_pbp([2022, 2023])
+            .unwrap()
+            .filter(col("posteam").eq(lit("KC")))
+            .select([col("season"), col("epa")])
+    };
+    let plan = scan().describe_optimized_plan().unwrap();
+    assert_eq!(plan.matches("Parquet SCAN").count(), 2, "{plan}");
+    assert_eq!(plan.matches("PROJECT 3/4 COLUMNS").count(), 2, "{plan}");
+    assert_eq!(
+        plan.matches("SELECTION: [(col(\"posteam\"))").count(),
+        2,
+        "{plan}"
+    );
+    let df = scan().collect().unwrap();
+    assert_eq!(df.get_column_names(), ["season", "epa"]);
+    assert_eq!(
+        df.column("season").unwrap().i32().unwrap().to_vec(),
+        [Some(2022), Some(2022), Some(2023), Some(2023)]
+    );
+    assert_eq!(server.hits(), 2);
+    let cached = dir.join("nflverse-data/pbp/play_by_play_2022.parquet");
+    assert!(cached.exists());
+
+    // A scan keeps reading the entries it was built over after they are evicted.
+    let pending = scan();
+    clear_cache(None);
+    assert!(!cached.exists());
+    assert!(pending.collect().unwrap().equals(&df));
+
+    // Raw entries whose footer no longer decodes are discarded before being
+    // scanned.
+    update_config(Config {
+        cache_raw: true,
+        ..config.clone()
+    });
+    let fresh = scan_pbp(2022).unwrap().collect().unwrap();
+    let mut truncated = std::fs::read(&cached).unwrap();
+    truncated.truncate(truncated.len() - 4);
+    std::fs::write(&cached, truncated).unwrap();
+    assert!(scan_pbp(2022).unwrap().collect().unwrap().equals(&fresh));
+    assert_eq!(server.hits(), 4);
+
+    // Outside filesystem mode downloads are scanned from temporary files, so
+    // the reads are still pruned.
+    config.cache_mode = CacheMode::Memory;
+    update_config(config);
+    let plan = scan().describe_optimized_plan().unwrap();
+    assert_eq!(plan.matches("PROJECT 3/4 COLUMNS").count(), 2, "{plan}");
+    assert!(scan().collect().unwrap().equals(&df));
+}
+
+#[test]
+fn test_scan_single_file_datasets() {
+    let (_guard, _dir) = filesystem_config("scan-single", 3600);
+    let games = df! {
+        "season" => [2022i32, 2023, 2023],
+        "game_id" => ["2022_01_KC_ARI", "2023_01_DET_KC", "2023_02_KC_JAX"],
+        "roof" => ["outdoors", "dome", "retractable"],
+    }
+    .unwrap();
+    let body = parquet_bytes(&games);
+    let server = TestServer::start(move |_, _| Response::ok(body.clone()));
+    let mut config = get_config();
+    config.base_urls = HashMap::from([(Repository::NflverseData, server.url.clone())]);
+    update_config(config);
+
+    let scan = || {
+        scan_schedules(2023)
+            .unwrap()
+            .select([col("game_id"), col("roof")])
+    };
+    let plan = scan().describe_optimized_plan().unwrap();
+    assert!(plan.contains("Parquet SCAN"), "{plan}");
+    assert!(plan.contains("SELECTION: "), "{plan}");
+    let df = scan().collect().unwrap();
+    assert_eq!(
+        df.column("game_id")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_iter()
+            .collect::<Vec<_>>(),
+        [Some("2023_01_DET_KC"), Some("2023_02_KC_JAX")]
+    );
+    assert_eq!(
+        df.column("roof")
+            .unwrap()
+            .str()
+            .unwrap()
+            .into_iter()
+            .collect::<Vec<_>>(),
+        [Some("dome"), None]
+    );
+    assert!(
+        df.equals_missing(
+            &load_schedules(2023)
+                .unwrap()
+                .select(["game_id", "roof"])
+                .unwrap()
+        )
+    );
+    assert_eq!(scan_teams().unwrap().collect().unwrap().height(), 3);
+}
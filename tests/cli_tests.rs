@@ -0,0 +1,314 @@
+//! Command-line interface tests. The binary is pointed at a local directory
+//! data source so no network access is needed.
+
+mod common;
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use common::{Response, TestServer, parquet_bytes, sample_df};
+use polars::prelude::*;
+
+/// A local nflverse-data tree with weekly player stats for 2022 and 2023.
+fn data_root(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!(
+        "nflreadrust-test-cli-{name}-{}",
+        std::process::id()
+    ));
+    std::fs::create_dir_all(root.join("stats_player")).unwrap();
+    for season in [2022i32, 2023] {
+        let df = df! {
+            "season" => [season; 3],
+            "week" => [1i32, 2, 3],
+            "team" => ["KC", "PHI", "KC"],
+        }
+        .unwrap();
+        std::fs::write(
+            root.join(format!("stats_player/stats_player_week_{season}.parquet")),
+            parquet_bytes(&df),
+        )
+        .unwrap();
+    }
+    root
+}
+
+fn nflreadrust(root: &Path, args: &[&str]) -> Output {
+    let root = root.to_str().unwrap();
+    run(
+        &[
+            ("NFLREADRUST_NFLVERSE_DATA_URL", root),
+            ("NFLREADRUST_CACHE", "off"),
+        ],
+        args,
+    )
+}
+
+fn run(envs: &[(&str, &str)], args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_nflreadrust"))
+        .args(args)
+        .envs(envs.iter().copied())
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_cli_writes_csv_to_stdout() {
+    let root = data_root("stdout");
+    let output = nflreadrust(
+        &root,
+        &[
+            "player-stats",
+            "--level",
+            "week",
+            "--seasons",
+            "2022-2023",
+            "--columns",
+            "season,week",
+            "--filter",
+            "team=KC",
+            "--filter",
+            "week>=2",
+        ],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "season,week\n2022,3\n2023,3\n"
+    );
+}
+
+#[test]
+fn test_cli_writes_output_file() {
+    let root = data_root("file");
+    let out = root.join("out.csv");
+    let output = nflreadrust(
+        &root,
+        &[
+            "player-stats",
+            "-s",
+            "2023",
+            "--limit",
+            "1",
+            "-o",
+            out.to_str().unwrap(),
+        ],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        std::fs::read_to_string(out).unwrap(),
+        "season,week,team\n2023,1,KC\n"
+    );
+}
+
+#[test]
+fn test_cli_exports_formats() {
+    let root = data_root("formats");
+
+    let out = root.join("stats.parquet");
+    let output = nflreadrust(
+        &root,
+        &[
+            "player-stats",
+            "-s",
+            "2022-2023",
+            "--compression",
+            "gzip",
+            "-o",
+            out.to_str().unwrap(),
+        ],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let df = ParquetReader::new(std::fs::File::open(&out).unwrap())
+        .finish()
+        .unwrap();
+    assert_eq!(df.height(), 6);
+
+    let out = root.join("stats.arrow");
+    let output = nflreadrust(
+        &root,
+        &["player-stats", "-s", "2023", "-o", out.to_str().unwrap()],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let df = IpcReader::new(std::fs::File::open(&out).unwrap())
+        .finish()
+        .unwrap();
+    assert_eq!(df.height(), 3);
+
+    let output = nflreadrust(
+        &root,
+        &[
+            "player-stats",
+            "-s",
+            "2023",
+            "-c",
+            "week,team",
+            "--limit",
+            "2",
+            "--format",
+            "ndjson",
+        ],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "{\"week\":1,\"team\":\"KC\"}\n{\"week\":2,\"team\":\"PHI\"}\n"
+    );
+
+    let out = root.join("stats.txt");
+    let output = nflreadrust(
+        &root,
+        &["player-stats", "-s", "2023", "-o", out.to_str().unwrap()],
+    );
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Cannot infer the output format"));
+}
+
+#[test]
+fn test_cli_reports_invalid_arguments() {
+    let root = data_root("invalid");
+
+    let output = nflreadrust(&root, &["player-stats", "--level", "monthly", "-s", "2023"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Must be one of: week, reg, post, reg+post"),
+        "{stderr}"
+    );
+
+    let output = nflreadrust(&root, &["player-stats", "-s", "2023", "-c", "nope"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Unknown column for player_stats: 'nope'"),
+        "{stderr}"
+    );
+
+    let output = nflreadrust(&root, &["--cache", "filesytem", "teams"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("must be one of: memory, filesystem, off"),
+        "{stderr}"
+    );
+
+    let output = nflreadrust(&root, &["player-stats", "-s", "2023-2020"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_cli_cache_commands() {
+    let cache_dir =
+        std::env::temp_dir().join(format!("nflreadrust-test-cli-cache-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&cache_dir);
+    let body = parquet_bytes(&sample_df());
+    let server = TestServer::start(move |_, _| Response::ok(body.clone()));
+    let envs = [
+        ("NFLREADRUST_NFLVERSE_DATA_URL", server.url.as_str()),
+        ("NFLREADRUST_CACHE", "filesystem"),
+        ("NFLREADRUST_CACHE_DIR", cache_dir.to_str().unwrap()),
+    ];
+
+    assert!(
+        run(&envs, &["teams", "-o", "/dev/null", "--format", "csv"])
+            .status
+            .success()
+    );
+    assert!(
+        run(&envs, &["schedules", "-o", "/dev/null", "--format", "csv"])
+            .status
+            .success()
+    );
+
+    let output = run(&envs, &["cache", "list"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout
+            .lines()
+            .any(|l| l.starts_with("teams ") && l.contains(" in ")),
+        "{stdout}"
+    );
+    assert!(stdout.contains("/schedules/games.parquet"), "{stdout}");
+
+    let output = run(&envs, &["cache", "list", "-d", "teams"]);
+    assert_eq!(String::from_utf8(output.stdout).unwrap().lines().count(), 2);
+    let output = run(&envs, &["cache", "list", "-d", "teamz"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Unknown dataset: 'teamz'. Must be one of: pbp,"),
+        "{stderr}"
+    );
+
+    let output = run(&envs, &["cache", "info"]);
+    assert!(
+        String::from_utf8(output.stdout)
+            .unwrap()
+            .contains("Entries:   2 (0 expired)")
+    );
+
+    let output = run(&envs, &["cache", "clear", "teams"]);
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Removed 1 cached entries for teams\n"
+    );
+
+    let envs_expired = [
+        envs[0],
+        envs[1],
+        envs[2],
+        ("NFLREADRUST_CACHE_DURATION", "0"),
+    ];
+    let output = run(&envs_expired, &["cache", "prune"]);
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Removed 1 expired entries\n"
+    );
+    let output = run(&envs, &["cache", "list"]);
+    assert_eq!(String::from_utf8(output.stdout).unwrap().lines().count(), 1);
+}
+
+#[test]
+fn test_cli_defaults_to_filesystem_cache() {
+    let cache_dir = std::env::temp_dir().join(format!(
+        "nflreadrust-test-cli-default-cache-{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&cache_dir);
+    let body = parquet_bytes(&sample_df());
+    let server = TestServer::start(move |_, _| Response::ok(body.clone()));
+    let teams = || {
+        Command::new(env!("CARGO_BIN_EXE_nflreadrust"))
+            .args(["teams", "-o", "/dev/null", "--format", "csv"])
+            .env("NFLREADRUST_NFLVERSE_DATA_URL", &server.url)
+            .env("NFLREADRUST_CACHE_DIR", &cache_dir)
+            .env_remove("NFLREADRUST_CACHE")
+            .output()
+            .unwrap()
+    };
+
+    // The second process is served from the cache the first one wrote.
+    assert!(teams().status.success());
+    assert!(teams().status.success());
+    assert_eq!(server.hits(), 1);
+}
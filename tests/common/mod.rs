@@ -0,0 +1,207 @@
+//! A minimal HTTP/1.1 server for exercising the downloader without network access.
+
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use polars::prelude::*;
+
+static CONFIG_LOCK: Mutex<()> = Mutex::new(());
+
+/// Serialize tests that change the global configuration.
+pub fn lock_config() -> MutexGuard<'static, ()> {
+    CONFIG_LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: HashMap<String, String>,
+}
+
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn ok(body: Vec<u8>) -> Self {
+        Response {
+            status: 200,
+            headers: Vec::new(),
+            body,
+        }
+    }
+
+    pub fn status(status: u16) -> Self {
+        Response {
+            status,
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Serve the part of `body` named by the request's `Range` header with a
+    /// 206, as a server supporting range requests would, or all of it.
+    pub fn ranged(request: &Request, body: &[u8]) -> Self {
+        let Some(spec) = request
+            .headers
+            .get("range")
+            .and_then(|r| r.strip_prefix("bytes="))
+        else {
+            return Response::ok(body.to_vec());
+        };
+        let len = body.len();
+        let (start, end) = match spec.split_once('-').unwrap() {
+            ("", suffix) => (len.saturating_sub(suffix.parse().unwrap()), len - 1),
+            (start, "") => (start.parse().unwrap(), len - 1),
+            (start, end) => (
+                start.parse().unwrap(),
+                end.parse::<usize>().unwrap().min(len - 1),
+            ),
+        };
+        Response {
+            status: 206,
+            headers: vec![(
+                "Content-Range".to_string(),
+                format!("bytes {start}-{end}/{len}"),
+            )],
+            body: body[start..=end].to_vec(),
+        }
+    }
+}
+
+pub struct TestServer {
+    pub url: String,
+    hits: Arc<AtomicUsize>,
+    peak_concurrency: Arc<AtomicUsize>,
+    pub requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl TestServer {
+    /// Serve every request with `handler` on a random local port, each
+    /// connection on its own thread.
+    /// The handler receives the request and the zero-based hit count.
+    pub fn start<F>(handler: F) -> Self
+    where
+        F: Fn(&Request, usize) -> Response + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let hits = Arc::new(AtomicUsize::new(0));
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak_concurrency = Arc::new(AtomicUsize::new(0));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handler = Arc::new(handler);
+
+        let thread_hits = Arc::clone(&hits);
+        let thread_peak = Arc::clone(&peak_concurrency);
+        let thread_requests = Arc::clone(&requests);
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { continue };
+                let hits = Arc::clone(&thread_hits);
+                let in_flight = Arc::clone(&in_flight);
+                let peak = Arc::clone(&thread_peak);
+                let requests = Arc::clone(&thread_requests);
+                let handler = Arc::clone(&handler);
+                std::thread::spawn(move || {
+                    let Some(request) = read_request(&stream) else {
+                        return;
+                    };
+                    let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(current, Ordering::SeqCst);
+                    let hit = hits.fetch_add(1, Ordering::SeqCst);
+                    let response = handler(&request, hit);
+                    requests.lock().unwrap().push(request);
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    write_response(stream, &response);
+                });
+            }
+        });
+
+        TestServer {
+            url,
+            hits,
+            peak_concurrency,
+            requests,
+        }
+    }
+
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::SeqCst)
+    }
+
+    /// The most requests the handler was ever serving at once.
+    pub fn peak_concurrency(&self) -> usize {
+        self.peak_concurrency.load(Ordering::SeqCst)
+    }
+}
+
+fn read_request(stream: &TcpStream) -> Option<Request> {
+    let mut reader = BufReader::new(stream.try_clone().ok()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).ok()?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).is_err() || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+
+    Some(Request {
+        method,
+        path,
+        headers,
+    })
+}
+
+fn write_response(mut stream: TcpStream, response: &Response) {
+    let mut head = format!(
+        "HTTP/1.1 {} Status\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        response.body.len()
+    );
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str("\r\n");
+    let _ = stream.write_all(head.as_bytes());
+    let _ = stream.write_all(&response.body);
+}
+
+/// A small DataFrame with a `season` column, for serving as parquet.
+pub fn sample_df() -> DataFrame {
+    df! {
+        "season" => [2022i32, 2023, 2024],
+        "team" => ["KC", "PHI", "DET"],
+    }
+    .unwrap()
+}
+
+pub fn parquet_bytes(df: &DataFrame) -> Vec<u8> {
+    let mut buf = Vec::new();
+    ParquetWriter::new(&mut buf)
+        .finish(&mut df.clone())
+        .unwrap();
+    buf
+}
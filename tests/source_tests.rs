@@ -0,0 +1,470 @@
+//! Data source configuration tests (base URL overrides and mirrors). These
+//! change the global configuration, so each test holds the config lock.
+
+mod common;
+
+use std::collections::HashMap;
+
+use common::{Response, TestServer, lock_config, parquet_bytes, sample_df};
+use nflreadrust::config::update_config;
+use nflreadrust::downloader::Repository;
+use nflreadrust::*;
+
+#[test]
+fn test_base_url_override_points_loaders_at_stand_in() {
+    let _guard = lock_config();
+
+    let body = parquet_bytes(&sample_df());
+    let server = TestServer::start(move |_, _| Response::ok(body.clone()));
+    update_config(Config {
+        base_urls: HashMap::from([(Repository::NflverseData, server.url.clone())]),
+        ..Config::default()
+    });
+
+    let df = load_draft_picks(2023).unwrap();
+    assert_eq!(df.height(), 1);
+    assert_eq!(
+        server.requests.lock().unwrap()[0].path,
+        "/draft_picks/draft_picks.parquet"
+    );
+    assert_eq!(
+        Repository::NflverseData.base_url(),
+        format!("{}/", server.url)
+    );
+    assert_eq!(
+        Repository::Dynastyprocess.base_url(),
+        Repository::Dynastyprocess.default_base_url()
+    );
+}
+
+#[test]
+fn test_mirrors_are_tried_in_order() {
+    let _guard = lock_config();
+
+    let body = parquet_bytes(&sample_df());
+    let primary = TestServer::start(|_, _| Response::status(404));
+    let broken_mirror = TestServer::start(|_, _| Response::status(404));
+    let mirror = TestServer::start(move |_, _| Response::ok(body.clone()));
+    update_config(Config {
+        base_urls: HashMap::from([(Repository::NflverseData, primary.url.clone())]),
+        mirrors: HashMap::from([(
+            Repository::NflverseData,
+            vec![broken_mirror.url.clone(), mirror.url.clone()],
+        )]),
+        ..Config::default()
+    });
+
+    let df = load_pbp(2023, None).unwrap();
+    assert_eq!(df.height(), 3);
+    assert_eq!(primary.hits(), 1);
+    assert_eq!(broken_mirror.hits(), 1);
+    assert_eq!(
+        mirror.requests.lock().unwrap()[0].path,
+        "/pbp/play_by_play_2023.parquet"
+    );
+}
+
+#[test]
+fn test_local_directory_data_source() {
+    let _guard = lock_config();
+
+    let root = std::env::temp_dir().join(format!("nflreadrust-test-local-{}", std::process::id()));
+    std::fs::create_dir_all(root.join("pbp")).unwrap();
+    std::fs::write(
+        root.join("pbp/play_by_play_2023.parquet"),
+        parquet_bytes(&sample_df()),
+    )
+    .unwrap();
+    update_config(Config {
+        base_urls: HashMap::from([(Repository::NflverseData, root.to_string_lossy().to_string())]),
+        ..Config::default()
+    });
+
+    let df = load_pbp(2023, None).unwrap();
+    assert_eq!(df.height(), 3);
+    assert!(Repository::NflverseData.base_url().starts_with("file://"));
+
+    match load_pbp(2022, None) {
+        Err(NflReadError::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::NotFound),
+        other => panic!("expected a missing-file error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_snapshot_mirrors_catalog_to_disk() {
+    let _guard = lock_config();
+
+    let body = parquet_bytes(&sample_df());
+    let server = TestServer::start(move |request, _| {
+        if request.path.contains("participation") {
+            Response::status(404)
+        } else {
+            Response::ok(body.clone())
+        }
+    });
+    update_config(Config {
+        base_urls: Repository::ALL
+            .into_iter()
+            .map(|repo| (repo, format!("{}/{}", server.url, repo.name())))
+            .collect(),
+        ..Config::default()
+    });
+    let dir =
+        std::env::temp_dir().join(format!("nflreadrust-test-snapshot-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let manifest = snapshot(&dir).unwrap();
+
+    let pbp = manifest
+        .files
+        .iter()
+        .find(|f| f.path == "nflverse-data/pbp/play_by_play_2023.parquet")
+        .expect("pbp 2023 in manifest");
+    let written = std::fs::read(dir.join(&pbp.path)).unwrap();
+    assert_eq!(pbp.size, written.len() as u64);
+    assert_eq!(pbp.md5, format!("{:x}", md5::compute(&written)));
+    assert!(dir.join("dynastyprocess/db_playerids.csv").exists());
+    assert!(
+        dir.join("ffopportunity/latest-data/ep_weekly_2023.parquet")
+            .exists()
+    );
+    assert!(dir.join("manifest.toml").exists());
+
+    assert!(!manifest.failures.is_empty());
+    assert!(
+        manifest
+            .failures
+            .iter()
+            .all(|f| f.dataset == "participation")
+    );
+}
+
+#[test]
+fn test_load_by_name_dispatches_with_defaults() {
+    let _guard = lock_config();
+
+    let body = parquet_bytes(&sample_df());
+    let server = TestServer::start(move |_, _| Response::ok(body.clone()));
+    update_config(Config {
+        base_urls: HashMap::from([(Repository::NflverseData, server.url.clone())]),
+        cache_mode: CacheMode::Off,
+        ..Config::default()
+    });
+
+    let params: LoadParams = toml::from_str("seasons = [2023]").unwrap();
+    let df = load("player_stats", &params).unwrap();
+    assert_eq!(df.height(), 3);
+
+    let params = LoadParams::new()
+        .seasons(vec![2024])
+        .param("stat_type", "rushing");
+    let df = load("nextgen_stats", &params).unwrap();
+    assert_eq!(df.height(), 1);
+
+    let params: LoadParams =
+        toml::from_str("dataset = \"team_stats\"\nseasons = 2022\nsummary_level = \"reg\"")
+            .unwrap();
+    let df = load_from_params(&params).unwrap();
+    assert_eq!(df.height(), 3);
+
+    let requests = server.requests.lock().unwrap();
+    let paths: Vec<&str> = requests.iter().map(|r| r.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        vec![
+            "/stats_player/stats_player_week_2023.parquet",
+            "/nextgen_stats/ngs_rushing.parquet",
+            "/stats_team/stats_team_reg_2022.parquet",
+        ]
+    );
+}
+
+#[test]
+fn test_load_selects_columns_per_season() {
+    let _guard = lock_config();
+
+    let root =
+        std::env::temp_dir().join(format!("nflreadrust-test-columns-{}", std::process::id()));
+    std::fs::create_dir_all(root.join("pbp")).unwrap();
+    let old = polars::df! {
+        "season" => [2022i32, 2022],
+        "posteam" => ["KC", "PHI"],
+    }
+    .unwrap();
+    let new = polars::df! {
+        "season" => [2023i32],
+        "posteam" => ["DET"],
+        "epa" => [0.75],
+    }
+    .unwrap();
+    std::fs::write(
+        root.join("pbp/play_by_play_2022.parquet"),
+        parquet_bytes(&old),
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("pbp/play_by_play_2023.parquet"),
+        parquet_bytes(&new),
+    )
+    .unwrap();
+    update_config(Config {
+        base_urls: HashMap::from([(Repository::NflverseData, root.to_string_lossy().to_string())]),
+        ..Config::default()
+    });
+
+    let df = load_pbp([2022, 2023], Some(&["epa", "posteam"])).unwrap();
+    assert_eq!(df.get_column_names(), ["epa", "posteam"]);
+    assert_eq!(df.height(), 3);
+    assert_eq!(
+        df.column("epa").unwrap().f64().unwrap().to_vec(),
+        [None, None, Some(0.75)]
+    );
+
+    match load_pbp(2022, Some(&["season", "epa"])) {
+        Err(NflReadError::InvalidParameter(msg)) => {
+            assert_eq!(msg, "Unknown column for pbp: 'epa'")
+        }
+        other => panic!("expected an unknown-column error, got {other:?}"),
+    }
+    assert!(matches!(
+        load_pbp(2023, Some(&[])),
+        Err(NflReadError::InvalidParameter(_))
+    ));
+
+    // Loads by name pass the selection to the loader.
+    let params = LoadParams::new()
+        .seasons([2022, 2023])
+        .columns(&["posteam"]);
+    let df = load("pbp", &params).unwrap();
+    assert_eq!(df.get_column_names(), ["posteam"]);
+    assert_eq!(df.height(), 3);
+}
+
+#[test]
+fn test_load_reads_only_selected_columns() {
+    let _guard = lock_config();
+
+    let server = TestServer::start(|request, _| {
+        let season: i32 = request.path.rsplit('_').next().unwrap()[..4]
+            .parse()
+            .unwrap();
+        let df = polars::df! {
+            "season" => [season; 2],
+            "posteam" => ["KC", "PHI"],
+            "epa" => [0.5, -0.25],
+        }
+        .unwrap();
+        Response::ok(parquet_bytes(&df))
+    });
+    update_config(Config {
+        base_urls: HashMap::from([(Repository::NflverseData, server.url.clone())]),
+        ..Config::default()
+    });
+    clear_cache(None);
+
+    // An unknown name fails on the most recent season alone.
+    match load_pbp([2021, 2022, 2023], Some(&["posteam", "epa_typo"])) {
+        Err(NflReadError::InvalidParameter(msg)) => {
+            assert_eq!(msg, "Unknown column for pbp: 'epa_typo'")
+        }
+        other => panic!("expected an unknown-column error, got {other:?}"),
+    }
+    assert_eq!(
+        server.requests.lock().unwrap()[0].path,
+        "/pbp/play_by_play_2023.parquet"
+    );
+    assert_eq!(server.hits(), 1);
+
+    // The memory cache keeps the projection, not the full-width frame.
+    let df = load_pbp([2022, 2023], Some(&["epa"])).unwrap();
+    assert_eq!(df.get_column_names(), ["epa"]);
+    assert_eq!(df.height(), 4);
+    let hits = server.hits();
+    assert_eq!(load_pbp([2022, 2023], Some(&["epa"])).unwrap(), df);
+    assert_eq!(server.hits(), hits);
+    let df = load_pbp(2023, None).unwrap();
+    assert_eq!(df.width(), 3);
+    assert_eq!(server.hits(), hits + 1);
+    clear_cache(None);
+}
+
+/// Five seasons of 10,000 plays each, one row group per season. The values
+/// are pseudo-random so that each row group stays large once compressed.
+fn season_parquet() -> Vec<u8> {
+    let seasons: Vec<i32> = (2020..2025).flat_map(|season| [season; 10_000]).collect();
+    let mut state = 1u64;
+    let values: Vec<f64> = (0..seasons.len())
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 11) as f64
+        })
+        .collect();
+    let mut df = polars::df! {
+        "season" => seasons,
+        "value" => values,
+    }
+    .unwrap();
+    let mut buf = Vec::new();
+    polars::prelude::ParquetWriter::new(&mut buf)
+        .with_row_group_size(Some(10_000))
+        .finish(&mut df)
+        .unwrap();
+    buf
+}
+
+#[test]
+fn test_single_file_loader_fetches_row_groups_with_ranges() {
+    let _guard = lock_config();
+
+    let body = season_parquet();
+    let len = body.len();
+    let served = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let server_served = std::sync::Arc::clone(&served);
+    let server = TestServer::start(move |request, _| {
+        let response = Response::ranged(request, &body);
+        server_served.fetch_add(response.body.len(), std::sync::atomic::Ordering::SeqCst);
+        response
+    });
+    update_config(Config {
+        base_urls: HashMap::from([(Repository::NflverseData, server.url.clone())]),
+        ..Config::default()
+    });
+    clear_cache(None);
+
+    let df = load_schedules(2023).unwrap();
+    assert_eq!(df.height(), 10_000);
+    let seasons = df.column("season").unwrap().i32().unwrap();
+    assert!(seasons.into_iter().all(|season| season == Some(2023)));
+    let requests = server.requests.lock().unwrap().len();
+    assert!(requests >= 2);
+    assert!(
+        server
+            .requests
+            .lock()
+            .unwrap()
+            .iter()
+            .all(|r| r.headers.contains_key("range"))
+    );
+    assert!(served.load(std::sync::atomic::Ordering::SeqCst) < len / 2);
+
+    // The selection is cached under its own key.
+    let again = load_schedules(2023).unwrap();
+    assert_eq!(again.height(), 10_000);
+    assert_eq!(server.hits(), requests);
+
+    let df = load_schedules([2020, 2024]).unwrap();
+    assert_eq!(df.height(), 20_000);
+
+    let url = format!("{}/schedules/games.parquet", server.url);
+    let df = downloader::download_parquet_seasons(&url, &[2021], None).unwrap();
+    assert_eq!(df.get_column_names(), ["season", "value"]);
+    assert_eq!(df.height(), 10_000);
+
+    // With a projection only the `season` chunk of the row group is fetched,
+    // and the selection is cached apart from the full one.
+    let before = served.load(std::sync::atomic::Ordering::SeqCst);
+    let df = downloader::download_parquet_seasons(&url, &[2022], Some(&["season"])).unwrap();
+    assert_eq!(df.get_column_names(), ["season"]);
+    assert_eq!(df.height(), 10_000);
+    let projected = served.load(std::sync::atomic::Ordering::SeqCst) - before;
+    let before = served.load(std::sync::atomic::Ordering::SeqCst);
+    let df = downloader::download_parquet_seasons(&url, &[2022], None).unwrap();
+    assert_eq!(df.get_column_names(), ["season", "value"]);
+    let full = served.load(std::sync::atomic::Ordering::SeqCst) - before;
+    assert!(projected + len / 10 < full, "{projected} vs {full}");
+
+    // `load` passes its columns on to the ranged read.
+    let before = served.load(std::sync::atomic::Ordering::SeqCst);
+    let params = LoadParams::new().seasons(2021).columns(&["season"]);
+    let df = load("schedules", &params).unwrap();
+    assert_eq!(df.get_column_names(), ["season"]);
+    assert_eq!(df.height(), 10_000);
+    let loaded = served.load(std::sync::atomic::Ordering::SeqCst) - before;
+    assert!(loaded + len / 10 < full, "{loaded} vs {full}");
+}
+
+#[test]
+fn test_range_requests_retry_and_fall_back_to_mirrors() {
+    let _guard = lock_config();
+
+    let body = season_parquet();
+    let primary = TestServer::start(|_, _| Response::status(404));
+    let mirror = TestServer::start(move |request, hit| {
+        if hit == 1 {
+            Response::status(503).header("Retry-After", "0")
+        } else {
+            Response::ranged(request, &body)
+        }
+    });
+    update_config(Config {
+        base_urls: HashMap::from([(Repository::NflverseData, primary.url.clone())]),
+        mirrors: HashMap::from([(Repository::NflverseData, vec![mirror.url.clone()])]),
+        ..Config::default()
+    });
+    clear_cache(None);
+
+    let df = load_schedules(2023).unwrap();
+    assert_eq!(df.height(), 10_000);
+    assert_eq!(primary.hits(), 1);
+    let requests = mirror.requests.lock().unwrap();
+    assert!(requests.len() >= 3);
+    assert!(requests.iter().all(|r| r.headers.contains_key("range")));
+}
+
+#[test]
+fn test_single_file_loader_falls_back_on_unusable_range_responses() {
+    let _guard = lock_config();
+
+    // A 206 without a Content-Range header, and row group ranges that come
+    // back as zeros, which decode as garbage pages.
+    for zeroed_row_groups in [false, true] {
+        let body = season_parquet();
+        let server = TestServer::start(move |request, _| {
+            let mut response = Response::ranged(request, &body);
+            let Some(range) = request.headers.get("range") else {
+                return response;
+            };
+            if !zeroed_row_groups {
+                response.headers.clear();
+            } else if !range.starts_with("bytes=-") {
+                response.body.fill(0);
+            }
+            response
+        });
+        update_config(Config {
+            base_urls: HashMap::from([(Repository::NflverseData, server.url.clone())]),
+            ..Config::default()
+        });
+        clear_cache(None);
+
+        let df = load_schedules(2023).unwrap();
+        assert_eq!(df.height(), 10_000);
+        let requests = server.requests.lock().unwrap();
+        assert!(requests.first().unwrap().headers.contains_key("range"));
+        assert!(!requests.last().unwrap().headers.contains_key("range"));
+    }
+}
+
+#[test]
+fn test_single_file_loader_falls_back_without_range_support() {
+    let _guard = lock_config();
+
+    let body = season_parquet();
+    let server = TestServer::start(move |_, _| Response::ok(body.clone()));
+    update_config(Config {
+        base_urls: HashMap::from([(Repository::NflverseData, server.url.clone())]),
+        ..Config::default()
+    });
+    clear_cache(None);
+
+    let df = load_schedules([2021, 2022]).unwrap();
+    assert_eq!(df.height(), 20_000);
+    assert_eq!(server.hits(), 1);
+
+    // The whole file was cached, so other seasons need no further requests.
+    let df = load_schedules(2024).unwrap();
+    assert_eq!(df.height(), 10_000);
+    assert_eq!(server.hits(), 1);
+}
@@ -1,3 +1,6 @@
+mod common;
+
+use common::{Response, TestServer, lock_config, parquet_bytes, sample_df};
 use nflreadrust::*;
 
 #[test]
@@ -19,10 +22,168 @@ fn test_config_default() {
     let config = Config::default();
     assert_eq!(config.cache_mode, CacheMode::Memory);
     assert_eq!(config.cache_duration, 86400);
+    assert_eq!(config.memory_cache_max_bytes, 0);
     assert!(!config.verbose);
     assert_eq!(config.timeout, 120);
 }
 
+#[test]
+fn test_config_download_concurrency_default() {
+    let config = Config::default();
+    assert_eq!(config.download_concurrency, 4);
+}
+
+#[test]
+fn test_download_all_fails_cleanly() {
+    use nflreadrust::config::update_config;
+    use nflreadrust::downloader::{DataFormat, download_all};
+
+    let _guard = lock_config();
+    let server = TestServer::start(|_, _| Response::status(500));
+    update_config(Config {
+        retry: RetryPolicy {
+            max_attempts: 1,
+            ..RetryPolicy::default()
+        },
+        ..Config::default()
+    });
+
+    let urls: Vec<String> = (2020..=2025)
+        .map(|s| format!("{}/failing/play_by_play_{s}.parquet", server.url))
+        .collect();
+    let result = download_all(&urls, DataFormat::Parquet);
+    update_config(Config::default());
+    assert!(result.is_err());
+    // No URL was requested more than once.
+    let mut paths: Vec<String> = server
+        .requests
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|r| r.path.clone())
+        .collect();
+    let hits = paths.len();
+    paths.sort();
+    paths.dedup();
+    assert!(hits >= 1);
+    assert_eq!(paths.len(), hits);
+}
+
+#[test]
+fn test_download_all_runs_concurrently_in_order() {
+    use nflreadrust::downloader::{DataFormat, download_all};
+
+    let seasons = [2024i32, 2020, 2023, 2021];
+    let server = TestServer::start(|request, _| {
+        let season: i32 = request.path.rsplit('_').next().unwrap()[..4]
+            .parse()
+            .unwrap();
+        // Earlier seasons answer last, so completion order differs from request order.
+        std::thread::sleep(std::time::Duration::from_millis(
+            50 + 10 * (2024 - season) as u64,
+        ));
+        Response::ok(parquet_bytes(
+            &polars::df! { "season" => [season] }.unwrap(),
+        ))
+    });
+    let urls: Vec<String> = seasons
+        .iter()
+        .map(|s| format!("{}/concurrency/season_{s}.parquet", server.url))
+        .collect();
+
+    let dfs = download_all(&urls, DataFormat::Parquet).unwrap();
+    let loaded: Vec<i32> = dfs
+        .iter()
+        .map(|df| df.column("season").unwrap().i32().unwrap().get(0).unwrap())
+        .collect();
+    assert_eq!(loaded, seasons);
+    assert!(
+        server.peak_concurrency() > 1,
+        "got {}",
+        server.peak_concurrency()
+    );
+}
+
+#[test]
+fn test_http_client_injection() {
+    use nflreadrust::client::{http_client, reset_http_clients, set_http_client};
+    use nflreadrust::downloader::{DataFormat, download_dataframe};
+
+    let _guard = lock_config();
+    let body = parquet_bytes(&sample_df());
+    let server = TestServer::start(move |_, _| Response::ok(body.clone()));
+
+    let custom = reqwest::blocking::Client::builder()
+        .user_agent("injected-client/1.0")
+        .build()
+        .unwrap();
+    set_http_client(custom);
+    let injected = download_dataframe(
+        &format!("{}/client/injected.parquet", server.url),
+        DataFormat::Parquet,
+    );
+    reset_http_clients();
+    assert_eq!(injected.unwrap().height(), 3);
+    download_dataframe(
+        &format!("{}/client/managed.parquet", server.url),
+        DataFormat::Parquet,
+    )
+    .unwrap();
+
+    let requests = server.requests.lock().unwrap();
+    assert_eq!(requests[0].headers["user-agent"], "injected-client/1.0");
+    assert_eq!(
+        requests[1].headers["user-agent"],
+        nflreadrust::config::get_config().user_agent
+    );
+    assert!(http_client().is_ok());
+}
+
+#[test]
+fn test_retry_policy_backoff() {
+    let policy = RetryPolicy {
+        jitter: false,
+        ..RetryPolicy::default()
+    };
+    assert_eq!(policy.backoff_delay(1).as_millis(), 500);
+    assert_eq!(policy.backoff_delay(2).as_millis(), 1000);
+    assert_eq!(policy.backoff_delay(3).as_millis(), 2000);
+    assert_eq!(policy.backoff_delay(20).as_millis(), 30_000);
+    assert!(policy.is_retriable_status(503));
+    assert!(!policy.is_retriable_status(404));
+
+    let jittered = RetryPolicy::default().backoff_delay(2).as_millis();
+    assert!((500..=1000).contains(&jittered), "got {jittered}");
+}
+
+#[test]
+fn test_download_retries_transient_status() {
+    use nflreadrust::downloader::{DataFormat, download_dataframe};
+
+    let body = parquet_bytes(&sample_df());
+    let server = TestServer::start(move |_, hit| {
+        if hit == 0 {
+            Response::status(503).header("Retry-After", "0")
+        } else {
+            Response::ok(body.clone())
+        }
+    });
+    let url = format!("{}/retry/flaky.parquet", server.url);
+    let df = download_dataframe(&url, DataFormat::Parquet).expect("retry should succeed");
+    assert_eq!(df.height(), 3);
+    assert_eq!(server.hits(), 2);
+}
+
+#[test]
+fn test_download_does_not_retry_not_found() {
+    use nflreadrust::downloader::{DataFormat, download_dataframe};
+
+    let server = TestServer::start(|_, _| Response::status(404));
+    let url = format!("{}/retry/missing.parquet", server.url);
+    assert!(download_dataframe(&url, DataFormat::Parquet).is_err());
+    assert_eq!(server.hits(), 1);
+}
+
 #[test]
 fn test_cache_mode_from_str() {
     assert_eq!(CacheMode::from_str("memory"), CacheMode::Memory);
@@ -32,6 +193,363 @@ fn test_cache_mode_from_str() {
     assert_eq!(CacheMode::from_str("unknown"), CacheMode::Memory);
 }
 
+#[test]
+fn test_catalog_lookup() {
+    assert_eq!(datasets().len(), 23);
+    let pbp = find_dataset("pbp").expect("pbp is in the catalog");
+    assert_eq!(pbp.first_season, Some(1999));
+    assert!(pbp.is_per_season());
+    assert_eq!(
+        pbp.paths(&[], &[2022, 2023]).unwrap(),
+        vec!["pbp/play_by_play_2022", "pbp/play_by_play_2023",]
+    );
+    assert!(!find_dataset("schedules").unwrap().is_per_season());
+    assert!(find_dataset("unknown").is_none());
+}
+
+#[test]
+fn test_catalog_parameters() {
+    let stats = find_dataset("player_stats").unwrap();
+    assert_eq!(stats.parameter("summary_level").unwrap().default, "week");
+    assert_eq!(
+        stats
+            .paths(&[("summary_level", "reg+post")], &[2023])
+            .unwrap(),
+        vec!["stats_player/stats_player_regpost_2023"]
+    );
+
+    let err = stats.validate_param("summary_level", "daily").unwrap_err();
+    assert!(matches!(err, NflReadError::InvalidParameter(_)));
+    assert!(err.to_string().contains("week, reg, post, reg+post"));
+    assert!(stats.validate_param("stat_type", "week").is_err());
+    assert!(stats.paths(&[("summary_level", "week")], &[1990]).is_err());
+
+    let pfr = find_dataset("pfr_advstats").unwrap();
+    let season_level = [("stat_type", "def"), ("summary_level", "season")];
+    assert_eq!(
+        pfr.path(&season_level).unwrap(),
+        "pfr_advstats/advstats_season_def"
+    );
+}
+
+#[test]
+fn test_parameter_enums_match_catalog() {
+    fn values<T: std::fmt::Display>(all: &[T]) -> Vec<String> {
+        all.iter().map(T::to_string).collect()
+    }
+    let catalog = |dataset: &str, param: &str| -> Vec<String> {
+        let dataset = find_dataset(dataset).unwrap();
+        let param = dataset.parameter(param).unwrap();
+        assert_eq!(param.values[0], param.default);
+        param.values.iter().map(|v| v.to_string()).collect()
+    };
+
+    assert_eq!(
+        values(SummaryLevel::ALL),
+        catalog("player_stats", "summary_level")
+    );
+    assert_eq!(
+        values(SummaryLevel::ALL),
+        catalog("team_stats", "summary_level")
+    );
+    assert_eq!(
+        values(NgsStatType::ALL),
+        catalog("nextgen_stats", "stat_type")
+    );
+    assert_eq!(
+        values(PfrStatType::ALL),
+        catalog("pfr_advstats", "stat_type")
+    );
+    assert_eq!(
+        values(PfrSummaryLevel::ALL),
+        catalog("pfr_advstats", "summary_level")
+    );
+    assert_eq!(
+        values(RankingType::ALL),
+        catalog("ff_rankings", "ranking_type")
+    );
+    assert_eq!(
+        values(OpportunityStatType::ALL),
+        catalog("ff_opportunity", "stat_type")
+    );
+    assert_eq!(
+        values(ModelVersion::ALL),
+        catalog("ff_opportunity", "model_version")
+    );
+
+    assert_eq!(
+        "reg+post".parse::<SummaryLevel>().unwrap(),
+        SummaryLevel::RegPost
+    );
+    assert_eq!(ModelVersion::V1.to_string(), "v1.0.0");
+    assert_eq!(SummaryLevel::default(), SummaryLevel::Week);
+    let err = "daily".parse::<SummaryLevel>().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid parameter: Invalid summary_level: 'daily'. Must be one of: week, reg, post, reg+post"
+    );
+}
+
+#[test]
+fn test_seasons_selector() {
+    assert_eq!("2023".parse::<Seasons>().unwrap(), Seasons::Single(2023));
+    assert_eq!(
+        "2020-2023".parse::<Seasons>().unwrap(),
+        Seasons::from(2020..=2023)
+    );
+    assert_eq!("2015..".parse::<Seasons>().unwrap(), Seasons::from(2015..));
+    assert_eq!("last:3".parse::<Seasons>().unwrap(), Seasons::Last(3));
+    assert_eq!("ALL".parse::<Seasons>().unwrap(), Seasons::All);
+    assert_eq!(
+        "2019,2021-2023".parse::<Seasons>().unwrap(),
+        Seasons::from(vec![2019, 2021, 2022, 2023])
+    );
+    assert!(matches!(
+        "last:0".parse::<Seasons>(),
+        Err(NflReadError::InvalidParameter(_))
+    ));
+    for invalid in ["", "20x3", "2023-2020", "2019,..", "current,2023"] {
+        assert!(
+            matches!(
+                invalid.parse::<Seasons>(),
+                Err(NflReadError::InvalidSeason(_))
+            ),
+            "{invalid} should not parse"
+        );
+    }
+    for seasons in [
+        Seasons::Current,
+        Seasons::All,
+        Seasons::from(2020..=2023),
+        Seasons::Last(3),
+    ] {
+        assert_eq!(seasons.to_string().parse::<Seasons>().unwrap(), seasons);
+    }
+    assert_eq!(Seasons::default(), Seasons::Current);
+
+    let pbp = find_dataset("pbp").unwrap();
+    let current = pbp.current_season();
+    assert_eq!(pbp.resolve_seasons(&Seasons::Current).unwrap(), [current]);
+    assert_eq!(pbp.resolve_seasons(&Seasons::All).unwrap()[0], 1999);
+    assert_eq!(
+        pbp.resolve_seasons(&Seasons::Since(current - 1)).unwrap(),
+        [current - 1, current]
+    );
+    assert_eq!(
+        pbp.resolve_seasons(&Seasons::Last(2)).unwrap(),
+        [current - 1, current]
+    );
+    assert_eq!(
+        pbp.resolve_seasons(&Seasons::Last(500)).unwrap().len() as i32,
+        current - 1998
+    );
+    assert_eq!(
+        pbp.resolve_seasons(&Seasons::Last(u32::MAX)).unwrap()[0],
+        1999
+    );
+    let schedules = find_dataset("schedules").unwrap();
+    assert_eq!(
+        schedules.resolve_seasons(&Seasons::Last(u32::MAX)).unwrap()[0],
+        1920
+    );
+    assert!(matches!(
+        pbp.resolve_seasons(&Seasons::Last(0)),
+        Err(NflReadError::InvalidParameter(_))
+    ));
+    for invalid in [
+        Seasons::from(1990),
+        Seasons::from(1990..=2000),
+        Seasons::from(1995..),
+        Seasons::Since(current + 1),
+        Seasons::Range(std::ops::RangeInclusive::new(2023, 2020)),
+    ] {
+        assert!(
+            matches!(
+                pbp.resolve_seasons(&invalid),
+                Err(NflReadError::InvalidSeason(_))
+            ),
+            "{invalid:?} should not resolve"
+        );
+    }
+}
+
+#[test]
+fn test_load_by_name_validation() {
+    let err = load("play_by_play", &LoadParams::new()).unwrap_err();
+    assert!(matches!(err, NflReadError::InvalidParameter(_)));
+    assert!(err.to_string().contains("pbp, participation, schedules"));
+
+    let params = LoadParams::new().param("summary_level", "monthly");
+    let err = load("player_stats", &params).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Must be one of: week, reg, post, reg+post")
+    );
+
+    let params = LoadParams::new().param("stat_type", "passing");
+    assert!(load("pbp", &params).is_err());
+    assert!(load("teams", &LoadParams::new().seasons(vec![2023])).is_err());
+    assert!(matches!(
+        load("pbp", &LoadParams::new().dataset("teams")),
+        Err(NflReadError::InvalidParameter(_))
+    ));
+    assert!(matches!(
+        load_from_params(&LoadParams::new()),
+        Err(NflReadError::InvalidParameter(_))
+    ));
+    assert!(matches!(
+        load("pbp", &LoadParams::new().seasons(vec![1990])),
+        Err(NflReadError::InvalidSeason(_))
+    ));
+}
+
+#[test]
+fn test_load_params_from_toml() {
+    let params: LoadParams = toml::from_str(
+        r#"
+        seasons = [2022, 2023]
+        summary_level = "reg+post"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(
+        params,
+        LoadParams::new()
+            .seasons(vec![2022, 2023])
+            .param("summary_level", "reg+post")
+    );
+
+    let params: LoadParams = toml::from_str(
+        r#"
+        dataset = "ff_rankings"
+        ranking_type = 7
+        hidden = true
+        ratio = 0.5
+        "#,
+    )
+    .unwrap();
+    assert_eq!(params.dataset.as_deref(), Some("ff_rankings"));
+    assert_eq!(
+        params.params,
+        LoadParams::new()
+            .param("ranking_type", "7")
+            .param("hidden", "true")
+            .param("ratio", "0.5")
+            .params
+    );
+
+    let params: LoadParams = toml::from_str(r#"seasons = "2015..""#).unwrap();
+    assert_eq!(params, LoadParams::new().seasons(2015..));
+    let params: LoadParams = toml::from_str("seasons = 2023").unwrap();
+    assert_eq!(params.seasons, Some(Seasons::Single(2023)));
+    assert!(toml::from_str::<LoadParams>(r#"seasons = "sometime""#).is_err());
+    assert_eq!(
+        toml::to_string(&LoadParams::new().seasons(Seasons::Last(3)))
+            .unwrap()
+            .trim(),
+        r#"seasons = "last:3""#
+    );
+}
+
+#[test]
+fn test_write_dataframe_formats() {
+    use polars::prelude::*;
+    use std::io::Cursor;
+
+    let mut df = df! {
+        "season" => [2023i32, 2024],
+        "team" => [Some("KC"), None],
+        "epa" => [0.5f64, f64::NAN],
+    }
+    .unwrap();
+
+    let mut ndjson = Vec::new();
+    write_dataframe(
+        &mut df,
+        &mut ndjson,
+        ExportFormat::Ndjson,
+        Compression::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        String::from_utf8(ndjson).unwrap(),
+        "{\"season\":2023,\"team\":\"KC\",\"epa\":0.5}\n\
+         {\"season\":2024,\"team\":null,\"epa\":null}\n"
+    );
+
+    let mut json = Vec::new();
+    write_dataframe(
+        &mut df,
+        &mut json,
+        ExportFormat::Json,
+        Compression::default(),
+    )
+    .unwrap();
+    assert!(
+        String::from_utf8(json)
+            .unwrap()
+            .starts_with("[{\"season\":2023,")
+    );
+
+    // Dates are written in ISO 8601 form.
+    let mut dates = df! {
+        "gameday" => [chrono::NaiveDate::from_ymd_opt(2023, 9, 7).unwrap()],
+    }
+    .unwrap();
+    let mut json = Vec::new();
+    write_dataframe(
+        &mut dates,
+        &mut json,
+        ExportFormat::Json,
+        Compression::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        String::from_utf8(json).unwrap(),
+        "[{\"gameday\":\"2023-09-07\"}]\n"
+    );
+
+    let mut parquet = Vec::new();
+    write_dataframe(
+        &mut df,
+        &mut parquet,
+        ExportFormat::Parquet,
+        Compression::Snappy,
+    )
+    .unwrap();
+    let read = ParquetReader::new(Cursor::new(parquet)).finish().unwrap();
+    assert!(read.equals_missing(&df));
+
+    let mut ipc = Vec::new();
+    write_dataframe(&mut df, &mut ipc, ExportFormat::Ipc, Compression::default()).unwrap();
+    let read = IpcReader::new(Cursor::new(ipc)).finish().unwrap();
+    assert!(read.equals_missing(&df));
+}
+
+#[test]
+fn test_export_format_parsing() {
+    use std::path::Path;
+
+    assert_eq!(
+        ExportFormat::from_path(Path::new("pbp.PARQUET")),
+        Some(ExportFormat::Parquet)
+    );
+    assert_eq!(
+        ExportFormat::from_path(Path::new("pbp.jsonl")),
+        Some(ExportFormat::Ndjson)
+    );
+    assert_eq!(
+        ExportFormat::from_path(Path::new("pbp.feather")),
+        Some(ExportFormat::Ipc)
+    );
+    assert_eq!(ExportFormat::from_path(Path::new("pbp")), None);
+    assert_eq!("gzip".parse::<Compression>().unwrap(), Compression::Gzip);
+    assert!(matches!(
+        "xml".parse::<ExportFormat>(),
+        Err(NflReadError::InvalidParameter(_))
+    ));
+}
+
 // Helper to get min/max of i32 column
 fn col_i32_min(df: &polars::prelude::DataFrame, col_name: &str) -> i32 {
     let col = df.column(col_name).unwrap();
@@ -76,7 +594,7 @@ fn test_load_trades() {
 
 #[test]
 fn test_load_schedules_all() {
-    let df = load_schedules(None).expect("Failed to load schedules");
+    let df = load_schedules(Seasons::All).expect("Failed to load schedules");
     assert!(df.height() > 0, "Schedules DataFrame should not be empty");
     let cols = df.get_column_names();
     assert!(cols.contains(&&polars::prelude::PlSmallStr::from("season")));
@@ -85,7 +603,7 @@ fn test_load_schedules_all() {
 
 #[test]
 fn test_load_schedules_filtered() {
-    let df = load_schedules(Some(vec![2023])).expect("Failed to load schedules for 2023");
+    let df = load_schedules(2023).expect("Failed to load schedules for 2023");
     assert!(df.height() > 0);
     assert_eq!(col_i32_min(&df, "season"), 2023);
     assert_eq!(col_i32_max(&df, "season"), 2023);
@@ -93,25 +611,25 @@ fn test_load_schedules_filtered() {
 
 #[test]
 fn test_load_draft_picks() {
-    let df = load_draft_picks(Some(vec![2023])).expect("Failed to load draft picks");
+    let df = load_draft_picks(2023).expect("Failed to load draft picks");
     assert!(df.height() > 0, "Draft picks should not be empty");
 }
 
 #[test]
 fn test_load_combine() {
-    let df = load_combine(Some(vec![2023])).expect("Failed to load combine");
+    let df = load_combine(2023).expect("Failed to load combine");
     assert!(df.height() > 0, "Combine should not be empty");
 }
 
 #[test]
 fn test_load_officials() {
-    let df = load_officials(Some(vec![2023])).expect("Failed to load officials");
+    let df = load_officials(2023).expect("Failed to load officials");
     assert!(df.height() > 0, "Officials should not be empty");
 }
 
 #[test]
 fn test_load_pbp_single_season() {
-    let df = load_pbp(Some(vec![2023])).expect("Failed to load PBP");
+    let df = load_pbp(2023, None).expect("Failed to load PBP");
     assert!(df.height() > 0, "PBP should not be empty");
     let cols = df.get_column_names();
     assert!(cols.contains(&&polars::prelude::PlSmallStr::from("play_id")));
@@ -119,111 +637,111 @@ fn test_load_pbp_single_season() {
 
 #[test]
 fn test_load_player_stats_week() {
-    let df =
-        load_player_stats(Some(vec![2023]), "week").expect("Failed to load player stats week");
+    let df = load_player_stats(2023, SummaryLevel::Week, None)
+        .expect("Failed to load player stats week");
     assert!(df.height() > 0);
 }
 
 #[test]
 fn test_load_player_stats_reg() {
     let df =
-        load_player_stats(Some(vec![2023]), "reg").expect("Failed to load player stats reg");
+        load_player_stats(2023, SummaryLevel::Reg, None).expect("Failed to load player stats reg");
     assert!(df.height() > 0);
 }
 
 #[test]
 fn test_load_player_stats_invalid_level() {
-    let result = load_player_stats(Some(vec![2023]), "invalid");
-    assert!(result.is_err());
+    let result = "invalid".parse::<SummaryLevel>();
+    assert!(matches!(result, Err(NflReadError::InvalidParameter(_))));
 }
 
 #[test]
 fn test_load_team_stats_week() {
-    let df = load_team_stats(Some(vec![2023]), "week").expect("Failed to load team stats week");
+    let df = load_team_stats(2023, SummaryLevel::Week).expect("Failed to load team stats week");
     assert!(df.height() > 0);
 }
 
 #[test]
 fn test_load_rosters() {
-    let df = load_rosters(Some(vec![2023])).expect("Failed to load rosters");
+    let df = load_rosters(2023).expect("Failed to load rosters");
     assert!(df.height() > 0, "Rosters should not be empty");
 }
 
 #[test]
 fn test_load_rosters_weekly() {
-    let df = load_rosters_weekly(Some(vec![2023])).expect("Failed to load weekly rosters");
+    let df = load_rosters_weekly(2023).expect("Failed to load weekly rosters");
     assert!(df.height() > 0);
 }
 
 #[test]
 fn test_load_snap_counts() {
-    let df = load_snap_counts(Some(vec![2023])).expect("Failed to load snap counts");
+    let df = load_snap_counts(2023).expect("Failed to load snap counts");
     assert!(df.height() > 0);
 }
 
 #[test]
 fn test_load_injuries() {
-    let df = load_injuries(Some(vec![2023])).expect("Failed to load injuries");
+    let df = load_injuries(2023).expect("Failed to load injuries");
     assert!(df.height() > 0);
 }
 
 #[test]
 fn test_load_depth_charts() {
-    let df = load_depth_charts(Some(vec![2023])).expect("Failed to load depth charts");
+    let df = load_depth_charts(2023).expect("Failed to load depth charts");
     assert!(df.height() > 0);
 }
 
 #[test]
 fn test_load_ftn_charting() {
-    let df = load_ftn_charting(Some(vec![2023])).expect("Failed to load FTN charting");
+    let df = load_ftn_charting(2023, None).expect("Failed to load FTN charting");
     assert!(df.height() > 0);
 }
 
 #[test]
 fn test_load_nextgen_stats_passing() {
-    let df = load_nextgen_stats(Some(vec![2023]), "passing")
+    let df = load_nextgen_stats(2023, NgsStatType::Passing)
         .expect("Failed to load nextgen stats passing");
     assert!(df.height() > 0);
 }
 
 #[test]
 fn test_load_nextgen_stats_receiving() {
-    let df = load_nextgen_stats(Some(vec![2023]), "receiving")
+    let df = load_nextgen_stats(2023, NgsStatType::Receiving)
         .expect("Failed to load nextgen stats receiving");
     assert!(df.height() > 0);
 }
 
 #[test]
 fn test_load_nextgen_stats_rushing() {
-    let df = load_nextgen_stats(Some(vec![2023]), "rushing")
+    let df = load_nextgen_stats(2023, NgsStatType::Rushing)
         .expect("Failed to load nextgen stats rushing");
     assert!(df.height() > 0);
 }
 
 #[test]
 fn test_load_nextgen_stats_invalid_type() {
-    let result = load_nextgen_stats(Some(vec![2023]), "invalid");
-    assert!(result.is_err());
+    let result = "invalid".parse::<NgsStatType>();
+    assert!(matches!(result, Err(NflReadError::InvalidParameter(_))));
 }
 
 #[test]
 fn test_load_pfr_advstats_week() {
-    let df = load_pfr_advstats(Some(vec![2023]), "pass", "week")
+    let df = load_pfr_advstats(2023, PfrStatType::Pass, PfrSummaryLevel::Week)
         .expect("Failed to load PFR advstats week");
     assert!(df.height() > 0);
 }
 
 #[test]
 fn test_load_pfr_advstats_season() {
-    let df = load_pfr_advstats(Some(vec![2023]), "pass", "season")
+    let df = load_pfr_advstats(2023, PfrStatType::Pass, PfrSummaryLevel::Season)
         .expect("Failed to load PFR advstats season");
     assert!(df.height() > 0);
 }
 
 #[test]
 fn test_load_pfr_advstats_all_types() {
-    for stat_type in &["pass", "rush", "rec", "def"] {
-        let df = load_pfr_advstats(Some(vec![2023]), stat_type, "week")
+    for &stat_type in PfrStatType::ALL {
+        let df = load_pfr_advstats(2023, stat_type, PfrSummaryLevel::Week)
             .unwrap_or_else(|_| panic!("Failed to load PFR advstats {stat_type}"));
         assert!(
             df.height() > 0,
@@ -234,14 +752,14 @@ fn test_load_pfr_advstats_all_types() {
 
 #[test]
 fn test_load_pfr_advstats_invalid_type() {
-    let result = load_pfr_advstats(Some(vec![2023]), "invalid", "week");
-    assert!(result.is_err());
+    let result = "invalid".parse::<PfrStatType>();
+    assert!(matches!(result, Err(NflReadError::InvalidParameter(_))));
 }
 
 #[test]
 fn test_load_pfr_advstats_invalid_level() {
-    let result = load_pfr_advstats(Some(vec![2023]), "pass", "invalid");
-    assert!(result.is_err());
+    let result = "invalid".parse::<PfrSummaryLevel>();
+    assert!(matches!(result, Err(NflReadError::InvalidParameter(_))));
 }
 
 #[test]
@@ -252,19 +770,19 @@ fn test_load_ff_playerids() {
 
 #[test]
 fn test_load_ff_rankings_draft() {
-    let df = load_ff_rankings("draft").expect("Failed to load FF rankings draft");
+    let df = load_ff_rankings(RankingType::Draft).expect("Failed to load FF rankings draft");
     assert!(df.height() > 0);
 }
 
 #[test]
 fn test_load_ff_rankings_invalid_type() {
-    let result = load_ff_rankings("invalid");
-    assert!(result.is_err());
+    let result = "invalid".parse::<RankingType>();
+    assert!(matches!(result, Err(NflReadError::InvalidParameter(_))));
 }
 
 #[test]
 fn test_load_pbp_multi_season() {
-    let df = load_pbp(Some(vec![2022, 2023])).expect("Failed to load PBP multi-season");
+    let df = load_pbp([2022, 2023], None).expect("Failed to load PBP multi-season");
     assert!(df.height() > 0);
     assert_eq!(col_i32_min(&df, "season"), 2022);
     assert_eq!(col_i32_max(&df, "season"), 2023);
@@ -278,7 +796,7 @@ fn test_clear_cache() {
 
 #[test]
 fn test_load_schedules_roof_cleaning() {
-    let df = load_schedules(None).expect("Failed to load schedules");
+    let df = load_schedules(Seasons::All).expect("Failed to load schedules");
     if df
         .get_column_names()
         .contains(&&polars::prelude::PlSmallStr::from("roof"))
@@ -313,53 +831,78 @@ fn test_get_current_week_schedule_based() {
 #[test]
 fn test_load_participation() {
     // Use 2023 which should be a completed season
-    let df = load_participation(Some(vec![2023])).expect("Failed to load participation");
+    let df = load_participation(2023, None).expect("Failed to load participation");
     assert!(df.height() > 0);
 }
 
 #[test]
 fn test_load_ff_opportunity() {
-    let df = load_ff_opportunity(Some(vec![2023]), "weekly", "latest")
+    let df = load_ff_opportunity(2023, OpportunityStatType::Weekly, ModelVersion::Latest)
         .expect("Failed to load FF opportunity");
     assert!(df.height() > 0);
 }
 
 #[test]
 fn test_load_ff_rankings_week() {
-    let df = load_ff_rankings("week").expect("Failed to load FF rankings week");
+    let df = load_ff_rankings(RankingType::Week).expect("Failed to load FF rankings week");
     assert!(df.height() > 0);
 }
 
 #[test]
 fn test_load_ff_rankings_all() {
-    let df = load_ff_rankings("all").expect("Failed to load FF rankings all");
+    let df = load_ff_rankings(RankingType::All).expect("Failed to load FF rankings all");
     assert!(df.height() > 0);
 }
 
 #[test]
 fn test_load_player_stats_post() {
-    let df = load_player_stats(Some(vec![2023]), "post")
+    let df = load_player_stats(2023, SummaryLevel::Post, None)
         .expect("Failed to load player stats post");
     assert!(df.height() > 0);
 }
 
 #[test]
 fn test_load_player_stats_regpost() {
-    let df = load_player_stats(Some(vec![2023]), "reg+post")
+    let df = load_player_stats(2023, SummaryLevel::RegPost, None)
         .expect("Failed to load player stats reg+post");
     assert!(df.height() > 0);
 }
 
 #[test]
 fn test_load_team_stats_reg() {
-    let df = load_team_stats(Some(vec![2023]), "reg")
-        .expect("Failed to load team stats reg");
+    let df = load_team_stats(2023, SummaryLevel::Reg).expect("Failed to load team stats reg");
     assert!(df.height() > 0);
 }
 
 #[test]
 fn test_invalid_season_before_first() {
     // PBP first season is 1999
-    let result = load_pbp(Some(vec![1990]));
+    let result = load_pbp(1990, None);
     assert!(result.is_err());
 }
+
+#[tokio::test]
+async fn test_load_teams_async() {
+    let df = load_teams_async()
+        .await
+        .expect("Failed to load teams async");
+    assert!(df.height() > 0, "Teams DataFrame should not be empty");
+}
+
+#[tokio::test]
+async fn test_load_pbp_async_multi_season() {
+    let df = load_pbp_async([2022, 2023], None)
+        .await
+        .expect("Failed to load PBP multi-season async");
+    assert_eq!(col_i32_min(&df, "season"), 2022);
+    assert_eq!(col_i32_max(&df, "season"), 2023);
+}
+
+#[tokio::test]
+async fn test_load_player_stats_async_invalid_level() {
+    let params = LoadParams::new()
+        .seasons(vec![2023])
+        .param("summary_level", "invalid");
+    let result = load_async("player_stats", &params).await;
+    assert!(matches!(result, Err(NflReadError::InvalidParameter(_))));
+}